@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::{
+    dart::DartGenerator,
+    go::GoGenerator,
+    kotlin::KotlinGenerator,
+    python::{PythonGenerator, PythonOptions},
+    rust::{RustGenerator, RustOptions},
+    swift::SwiftGenerator,
+    typescript::TypeScriptGenerator,
+    udl::LangGenerator,
+};
+
+/// Unified Design Language code generator
+#[derive(Debug, Parser)]
+#[command(name = "udl", version, about)]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum Command {
+    /// Generate code for the selected targets
+    Generate(GenerateArgs),
+    /// Validate manifests and report every problem without generating code
+    Check(InputArgs),
+    /// Rewrite manifests in canonical form
+    Fmt(FmtArgs),
+    /// Generate Markdown documentation for manifests
+    Docs(OutputArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct InputArgs {
+    /// UDL manifest files
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct OutputArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Directory generated files are written to
+    #[arg(short, long, default_value = "gen")]
+    pub out_dir: PathBuf,
+
+    /// File name (without extension) of the generated files.
+    /// Defaults to the name of the input manifest.
+    #[arg(short = 'n', long)]
+    pub out_name: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct GenerateArgs {
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Languages to generate code for
+    #[arg(
+        short,
+        long = "target",
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Target::Dart, Target::Rust]
+    )]
+    pub targets: Vec<Target>,
+
+    /// Skip running the language formatter on generated files
+    #[arg(long)]
+    pub no_format: bool,
+
+    #[command(flatten)]
+    pub rust: RustOptions,
+
+    #[command(flatten)]
+    pub python: PythonOptions,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct FmtArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Exit with an error if any manifest is not formatted, without writing
+    #[arg(long, conflicts_with = "write")]
+    pub check: bool,
+
+    /// Overwrite manifests in place instead of printing to stdout.
+    /// Comments are not preserved.
+    #[arg(short, long)]
+    pub write: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Target {
+    Dart,
+    Rust,
+    #[value(name = "typescript", alias = "ts")]
+    TypeScript,
+    Kotlin,
+    Swift,
+    Python,
+    Go,
+}
+
+impl Target {
+    pub fn generator(&self, args: &GenerateArgs) -> Box<dyn LangGenerator> {
+        match self {
+            Target::Dart => Box::new(DartGenerator()),
+            Target::Rust => Box::new(RustGenerator(args.rust.clone())),
+            Target::TypeScript => Box::new(TypeScriptGenerator()),
+            Target::Kotlin => Box::new(KotlinGenerator()),
+            Target::Swift => Box::new(SwiftGenerator()),
+            Target::Python => Box::new(PythonGenerator(args.python.clone())),
+            Target::Go => Box::new(GoGenerator()),
+        }
+    }
+}
+
+impl OutputArgs {
+    /// Output path, without extension, for the given input manifest.
+    pub fn out_path(&self, input: &std::path::Path) -> PathBuf {
+        let name = match &self.out_name {
+            Some(name) => name.clone(),
+            None => input
+                .file_prefix()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "output".to_string()),
+        };
+        self.out_dir.join(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        python::PythonModels,
+        rust::{DateTimeCrate, RenameRule},
+    };
+
+    #[test]
+    fn test_generate_args() {
+        let cli = Cli::parse_from([
+            "udl",
+            "generate",
+            "examples/billing_app.yaml",
+            "--target",
+            "rust",
+            "-o",
+            "out",
+            "--rust-ordered-collections",
+            "--rust-datetime",
+            "time",
+            "--rust-rename-all",
+            "camelCase",
+        ]);
+        let Command::Generate(args) = cli.command else {
+            panic!("expected generate command");
+        };
+        assert_eq!(args.targets, vec![Target::Rust]);
+        assert!(args.rust.rust_ordered_collections);
+        assert_eq!(args.rust.rust_datetime, DateTimeCrate::Time);
+        assert_eq!(args.rust.rust_rename_all, RenameRule::Camel);
+        assert_eq!(
+            args.output.out_path(&args.output.input.inputs[0]),
+            PathBuf::from("out/billing_app")
+        );
+
+        let cli = Cli::parse_from(["udl", "generate", "a.udl.yaml", "-t", "dart,ts"]);
+        let Command::Generate(args) = cli.command else {
+            panic!("expected generate command");
+        };
+        assert_eq!(args.targets, vec![Target::Dart, Target::TypeScript]);
+
+        let cli = Cli::parse_from([
+            "udl",
+            "generate",
+            "a.udl.yaml",
+            "-t",
+            "python",
+            "--python-models",
+            "pydantic",
+        ]);
+        let Command::Generate(args) = cli.command else {
+            panic!("expected generate command");
+        };
+        assert_eq!(args.targets, vec![Target::Python]);
+        assert_eq!(args.python.python_models, PythonModels::Pydantic);
+
+        let cli = Cli::parse_from(["udl", "generate", "a.udl.yaml", "-n", "models"]);
+        let Command::Generate(args) = cli.command else {
+            panic!("expected generate command");
+        };
+        assert_eq!(args.targets, vec![Target::Dart, Target::Rust]);
+        assert_eq!(args.rust.rust_datetime, DateTimeCrate::Chrono);
+        assert_eq!(args.python.python_models, PythonModels::Dataclass);
+        assert_eq!(
+            args.output.out_path(&args.output.input.inputs[0]),
+            PathBuf::from("gen/models")
+        );
+    }
+}
@@ -1,16 +1,20 @@
-use core::panic;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     sync::LazyLock,
 };
 
 use convert_case::ccase;
+use indexmap::IndexMap;
 
 use crate::udl::{
-    LangGenerator, UDL,
+    LangGenerator, UDL, UdlError,
     class::{Class, Property, PropertyKey},
-    enums::{Enum, EnumKind, EnumVariantValue},
-    utils::{extract_enum_variant, is_nullable_type, parse_limit_validator},
+    constraints::{Check, VALIDATORS, constraints, is_validated},
+    core::TypeDefault,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::{parse_type, primitive_imports},
 };
 
 pub(crate) struct DartGenerator();
@@ -36,43 +40,510 @@ static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
         ("datetime", "DateTime"),
         ("date", "DateTime"),
         ("object", "Object"),
+        ("uuid", "String"),
+        ("decimal", "Decimal"),
+        ("bytes", "Uint8List"),
+        ("url", "Uri"),
+        ("duration", "Duration"),
+        ("email", "String"),
+    ])
+});
+
+/// Imports needed by primitives that are not part of `dart:core`.
+static IMPORTS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("decimal", "import 'package:decimal/decimal.dart';\n"),
+        ("bytes", "import 'dart:typed_data';\n"),
     ])
 });
 
 static NUMBER_TYPES: LazyLock<Vec<&str>> = std::sync::LazyLock::new(|| vec!["int", "double"]);
 
-const VALIDATORS: [PropertyKey; 5] = [
-    PropertyKey::Limit,
-    PropertyKey::Format,
-    PropertyKey::Default,
-    PropertyKey::Min,
-    PropertyKey::Max,
+/// Dart type without the nullable marker.
+fn dart_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "void".to_string(),
+        // `List`, `Set` and `Map` are Dart's own collection names
+        _ => format!(
+            "{}<{}>",
+            ty.name(),
+            ty.args()
+                .into_iter()
+                .map(dart_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn dart_type(ty: &TypeExpr) -> String {
+    format!(
+        "{}{}",
+        dart_base_type(ty),
+        if ty.nullable { "?" } else { "" }
+    )
+}
+
+/// Raw Dart string literal of `value`, falling back to an escaped one when it
+/// contains both kinds of quotes.
+fn dart_raw_string(value: &str) -> String {
+    if !value.contains('\'') {
+        format!("r'{}'", value)
+    } else if !value.contains('"') {
+        format!("r\"{}\"", value)
+    } else {
+        format!(
+            "'{}'",
+            value
+                .replace('\\', "\\\\")
+                .replace('\'', "\\'")
+                .replace('$', "\\$")
+        )
+    }
+}
+
+/// Reserved words, which can't be used as names.
+const KEYWORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
 ];
 
-fn clean_type_name(type_name: &str) -> String {
-    type_name
-        .replace("?", "")
-        .replace("^", "")
-        .replace("$enum::", "")
+/// camelCase Dart name of a field, param, enum value or method, with a trailing `$`
+/// when it is a reserved word, e.g. `class$`. JSON keys keep the plain name.
+fn dart_name(name: &str) -> String {
+    let name = ccase!(camel, name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{}$", name)
+    } else {
+        name
+    }
 }
 
-fn process_type(type_name: &str) -> (String, String) {
-    let is_nullable = is_nullable_type(type_name);
-    let name = clean_type_name(type_name);
-    let mapped_type = MAPPINGS.get(name.as_str());
-    let suffix = if is_nullable { "?" } else { "" };
-    (
-        format!("{}", mapped_type.unwrap_or(&name.as_str())),
-        suffix.into(),
+/// Escaped Dart string literal of `value`.
+fn dart_string(value: &str) -> String {
+    format!(
+        "'{}'",
+        value
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('$', "\\$")
     )
 }
 
+/// Dart literal of a resolved default, usable as a const constructor default.
+/// Defaults of tagged enums are unit variants, constructed without arguments.
+fn dart_default(default: &TypeDefault, ty: &TypeExpr) -> String {
+    match default {
+        TypeDefault::Variant(enumm, variant) if enumm.is_tagged() => {
+            format!("const {}()", variant_class(enumm, variant))
+        }
+        TypeDefault::Variant(enumm, variant) => {
+            format!("{}.{}", enumm.id, dart_name(variant.id()))
+        }
+        TypeDefault::Value(value) if value == "null" && ty.nullable => "null".to_string(),
+        TypeDefault::Value(value) if dart_base_type(ty) == "String" => dart_string(value),
+        TypeDefault::Value(value) => value.to_string(),
+    }
+}
+
+/// Dart class of a variant of a tagged enum, e.g. `PaymentKBank` for `K_BANK`.
+fn variant_class(enumm: &Enum, variant: &EnumKind) -> String {
+    format!("{}{}", enumm.id, ccase!(pascal, variant.id()))
+}
+
+/// Sealed class of an enum whose variants have fields, with a subclass per variant.
+/// JSON objects carry the variant's wire value in `type` next to its fields, like
+/// the Rust enum's `#[serde(tag = "type")]`.
+fn gen_tagged_enum(udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+    let mut imports = BTreeSet::new();
+    let mut code = String::new();
+    if let Some(desc) = &enumm.description {
+        code.push_str(&format!("/// {}\n", desc));
+    }
+    code.push_str(&format!(
+        "sealed class {} {{\n    const {}();\n\n",
+        enumm.id, enumm.id
+    ));
+    code.push_str(&format!(
+        "    factory {}.fromJson(Map<String, dynamic> json) => switch (json['type']) {{ {} \
+         final type => throw ArgumentError.value(type, 'type'), }};\n\n",
+        enumm.id,
+        enumm
+            .variants
+            .iter()
+            .map(|variant| format!(
+                "{} => {}.fromJson(json),",
+                dart_string(variant.wire_value()),
+                variant_class(enumm, variant)
+            ))
+            .collect::<Vec<_>>()
+            .join(" ")
+    ));
+    code.push_str("    Map<String, dynamic> toJson();\n}\n");
+
+    for variant in &enumm.variants {
+        let class = variant_class(enumm, variant);
+        // Field name, wire name and type, like the fields of a model
+        let fields = variant
+            .fields()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, ty)| Some((dart_name(key), ccase!(snake, key), parse_type(ty)?)))
+            .collect::<Vec<_>>();
+        code.push('\n');
+        if let EnumKind::Complex(EnumVariant {
+            description: Some(desc),
+            ..
+        }) = variant
+        {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        code.push_str(&format!("class {} extends {} {{", class, enumm.id));
+        for (name, _, ty) in &fields {
+            primitive_imports(ty, &IMPORTS, &mut imports);
+            code.push_str(&format!("final {} {};", dart_type(ty), name));
+        }
+        code.push_str("\n\n");
+        if fields.is_empty() {
+            code.push_str(&format!("    const {}();\n\n", class));
+        } else {
+            code.push_str(&format!(
+                "    const {}({{ {} }});\n\n",
+                class,
+                fields
+                    .iter()
+                    .map(|(name, _, _)| format!("required this.{}", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        code.push_str(&format!(
+            "    factory {}.fromJson(Map<String, dynamic> json) => {}{}({});\n\n",
+            class,
+            if fields.is_empty() { "const " } else { "" },
+            class,
+            fields
+                .iter()
+                .map(|(name, wire, ty)| format!(
+                    "{}: {}",
+                    name,
+                    dart_from_json(udl, &format!("json['{}']", wire), ty)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        code.push_str(&format!(
+            "    @override\n    Map<String, dynamic> toJson() => {{ 'type': {}{} }};\n\n",
+            dart_string(variant.wire_value()),
+            fields
+                .iter()
+                .map(|(name, wire, ty)| format!(", '{}': {}", wire, dart_to_json(udl, name, ty)))
+                .collect::<String>()
+        ));
+        for (_, _, ty) in &fields {
+            ty.visit(&mut |ty| {
+                if ty.kind == TypeKind::Primitive("bytes".to_string()) {
+                    imports.insert("import 'dart:convert';\n".to_string());
+                }
+            });
+        }
+        let fields = fields
+            .iter()
+            .map(|(name, _, ty)| (name.as_str(), name.clone(), ty))
+            .collect::<Vec<_>>();
+        code.push_str(&dart_equality(&class, &fields, &mut imports));
+        code.push_str("}\n");
+    }
+    (code, imports)
+}
+
+/// Zero value of a type, used for optional fields without a default.
+fn dart_zero_value(ty: &TypeExpr) -> Option<String> {
+    let zero = match &ty.kind {
+        TypeKind::Primitive(_) => match dart_base_type(ty).as_str() {
+            "bool" => "false",
+            "int" => "0",
+            "double" => "0.0",
+            "String" => "''",
+            _ => return None,
+        },
+        TypeKind::List(_) => "const []",
+        TypeKind::Set(_) | TypeKind::Map(..) => "const {}",
+        _ => return None,
+    };
+    Some(zero.to_string())
+}
+
+/// Parameter list of a service method. A single param is positional, inline params
+/// are named and required unless nullable.
+fn method_params(params: &[(Option<&str>, TypeExpr)]) -> String {
+    match params {
+        [(None, ty)] => format!("{} {}", dart_type(ty), dart_name(param_name(None, ty))),
+        [] => String::new(),
+        params => format!(
+            "{{ {} }}",
+            params
+                .iter()
+                .map(|(name, ty)| format!(
+                    "{}{} {}",
+                    if ty.nullable { "" } else { "required " },
+                    dart_type(ty),
+                    dart_name(param_name(*name, ty))
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Whether values of `ty` are compared with `DeepCollectionEquality`, which includes
+/// decoded JSON in `object` fields.
+fn is_collection(ty: &TypeExpr) -> bool {
+    match &ty.kind {
+        TypeKind::List(_) | TypeKind::Set(_) | TypeKind::Map(..) => true,
+        TypeKind::Primitive(name) => matches!(name.as_str(), "bytes" | "object"),
+        _ => false,
+    }
+}
+
+/// `copyWith`, `==`, `hashCode` and `toString` of a model, given the parameter name,
+/// field name and type of each property. Nullable parameters of `copyWith` default
+/// to the `_undefined` sentinel, so passing `null` clears the field. Validated models
+/// are copied through `build()` and return its result.
+fn dart_value_methods(
+    class: &Class,
+    fields: &[(&str, String, &TypeExpr)],
+    imports: &mut BTreeSet<String>,
+) -> String {
+    let mut code = String::new();
+    let params = fields
+        .iter()
+        .map(|(name, _, ty)| {
+            if ty.nullable {
+                format!("Object? {} = _undefined", name)
+            } else {
+                format!("{}? {}", dart_type(ty), name)
+            }
+        })
+        .collect::<Vec<_>>();
+    let args = fields
+        .iter()
+        .map(|(name, field, ty)| {
+            let current = if name == field {
+                format!("this.{}", field)
+            } else {
+                field.clone()
+            };
+            if ty.nullable {
+                format!(
+                    "{}: identical({}, _undefined) ? {} : {} as {}",
+                    name,
+                    name,
+                    current,
+                    name,
+                    dart_type(ty)
+                )
+            } else {
+                format!("{}: {} ?? {}", name, name, current)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let params = if params.is_empty() {
+        String::new()
+    } else {
+        format!("{{ {} }}", params.join(", "))
+    };
+    match &class.error {
+        Some(error) if is_validated(class) => code.push_str(&format!(
+            "    ResultDart<{}, {}> copyWith({}) => build({});\n\n",
+            class.id, error, params, args
+        )),
+        _ => code.push_str(&format!(
+            "    {} copyWith({}) => {}({});\n\n",
+            class.id, params, class.id, args
+        )),
+    }
+
+    code.push_str(&dart_equality(&class.id, fields, imports));
+    code
+}
+
+/// `==`, `hashCode` and `toString` of the class `id` comparing `fields`, see
+/// [`dart_value_methods`].
+fn dart_equality(
+    id: &str,
+    fields: &[(&str, String, &TypeExpr)],
+    imports: &mut BTreeSet<String>,
+) -> String {
+    let mut code = String::new();
+    if fields.iter().any(|(_, _, ty)| is_collection(ty)) {
+        imports.insert("import 'package:collection/collection.dart';\n".to_string());
+    }
+    code.push_str(&format!(
+        "    @override\n    bool operator ==(Object other) => identical(this, other) || other is {}{};\n\n",
+        id,
+        fields
+            .iter()
+            .map(|(_, field, ty)| if is_collection(ty) {
+                format!(
+                    " && const DeepCollectionEquality().equals({}, other.{})",
+                    field, field
+                )
+            } else {
+                format!(" && {} == other.{}", field, field)
+            })
+            .collect::<String>()
+    ));
+    code.push_str(&format!(
+        "    @override\n    int get hashCode => Object.hashAll([{}]);\n\n",
+        fields
+            .iter()
+            .map(|(_, field, ty)| if is_collection(ty) {
+                format!("const DeepCollectionEquality().hash({})", field)
+            } else {
+                field.clone()
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    code.push_str(&format!(
+        "    @override\n    String toString() => '{}({})';\n",
+        id,
+        fields
+            .iter()
+            .map(|(name, field, _)| if field.contains('$') {
+                format!("{}: ${{{}}}", name.trim_end_matches('$'), field)
+            } else {
+                format!("{}: ${}", name, field)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    code
+}
+
+/// Whether the enum `id` has variants with fields, and so is a sealed class.
+fn is_tagged(udl: &UDL, id: &str) -> bool {
+    udl.enums.iter().any(|e| e.id == id && e.is_tagged())
+}
+
+/// Dart expression decoding the JSON value `expr` into `ty`.
+fn dart_from_json(udl: &UDL, expr: &str, ty: &TypeExpr) -> String {
+    let cast = |ty: &str| format!("{} as {}", expr, ty);
+    let decoded = match &ty.kind {
+        TypeKind::Primitive(name) => match name.as_str() {
+            "datetime" | "date" => format!("DateTime.parse({})", cast("String")),
+            "decimal" => format!("Decimal.parse({})", cast("String")),
+            "url" => format!("Uri.parse({})", cast("String")),
+            "bytes" => format!("base64Decode({})", cast("String")),
+            "duration" => format!("Duration(milliseconds: ({}).toInt())", cast("num")),
+            _ => match dart_base_type(ty).as_str() {
+                "int" => format!("({}).toInt()", cast("num")),
+                "double" => format!("({}).toDouble()", cast("num")),
+                base => return format!("{}{}", cast(base), if ty.nullable { "?" } else { "" }),
+            },
+        },
+        TypeKind::Enum(name) if is_tagged(udl, name) => {
+            format!("{}.fromJson({})", name, cast("Map<String, dynamic>"))
+        }
+        TypeKind::Enum(name) => format!("{}.fromValue({})", name, cast("String")),
+        TypeKind::Model(name) => {
+            format!("{}.fromJson({})", name, cast("Map<String, dynamic>"))
+        }
+        TypeKind::List(item) | TypeKind::Set(item) => format!(
+            "({}).map((e) => {}).{}()",
+            cast("List<dynamic>"),
+            dart_from_json(udl, "e", item),
+            if matches!(ty.kind, TypeKind::Set(_)) {
+                "toSet"
+            } else {
+                "toList"
+            }
+        ),
+        TypeKind::Map(key, value) => {
+            // JSON object keys are always strings
+            let key = match dart_base_type(key).as_str() {
+                "String" => "k".to_string(),
+                "int" => "int.parse(k)".to_string(),
+                "double" => "double.parse(k)".to_string(),
+                "bool" => "k == 'true'".to_string(),
+                _ => dart_from_json(udl, "k", key),
+            };
+            format!(
+                "({}).map((k, v) => MapEntry({}, {}))",
+                cast("Map<String, dynamic>"),
+                key,
+                dart_from_json(udl, "v", value)
+            )
+        }
+        TypeKind::Generic { .. } | TypeKind::Void => cast(&dart_type(ty)),
+    };
+    if ty.nullable {
+        format!("{} == null ? null : {}", expr, decoded)
+    } else {
+        decoded
+    }
+}
+
+/// Dart expression encoding `expr` of type `ty` into a JSON value. Datetimes are
+/// written in UTC, since a local `DateTime` has no offset in ISO 8601.
+fn dart_to_json(udl: &UDL, expr: &str, ty: &TypeExpr) -> String {
+    let q = if ty.nullable { "?" } else { "" };
+    match &ty.kind {
+        TypeKind::Primitive(name) => match name.as_str() {
+            "datetime" => format!("{}{}.toUtc().toIso8601String()", expr, q),
+            "date" => format!("{}{}.toIso8601String().substring(0, 10)", expr, q),
+            "decimal" | "url" => format!("{}{}.toString()", expr, q),
+            "duration" => format!("{}{}.inMilliseconds", expr, q),
+            "bytes" if ty.nullable => format!("{} == null ? null : base64Encode({}!)", expr, expr),
+            "bytes" => format!("base64Encode({})", expr),
+            _ => expr.to_string(),
+        },
+        TypeKind::Enum(name) if is_tagged(udl, name) => format!("{}{}.toJson()", expr, q),
+        TypeKind::Enum(_) => format!("{}{}.value", expr, q),
+        TypeKind::Model(_) => format!("{}{}.toJson()", expr, q),
+        TypeKind::List(item) | TypeKind::Set(item) => match dart_to_json(udl, "e", item).as_str() {
+            "e" if matches!(ty.kind, TypeKind::List(_)) => expr.to_string(),
+            "e" => format!("{}{}.toList()", expr, q),
+            item => format!("{}{}.map((e) => {}).toList()", expr, q, item),
+        },
+        TypeKind::Map(key, value) => {
+            // JSON object keys are always strings
+            let key = match (&key.kind, dart_base_type(key).as_str()) {
+                (TypeKind::Enum(_), _) => "k.value".to_string(),
+                (_, "String") => "k".to_string(),
+                (_, "DateTime") => dart_to_json(udl, "k", key),
+                _ => "k.toString()".to_string(),
+            };
+            match (key.as_str(), dart_to_json(udl, "v", value).as_str()) {
+                ("k", "v") => expr.to_string(),
+                (key, value) => {
+                    format!("{}{}.map((k, v) => MapEntry({}, {}))", expr, q, key, value)
+                }
+            }
+        }
+        TypeKind::Generic { .. } | TypeKind::Void => expr.to_string(),
+    }
+}
+
 impl LangGenerator for DartGenerator {
     fn extension(&self) -> &str {
         "dart"
     }
 
-    fn gen_enum(&self, enumm: &Enum) -> String {
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        if enumm.is_tagged() {
+            return gen_tagged_enum(udl, enumm);
+        }
         let mut code = String::new();
         if let Some(desc) = enumm.description.as_ref() {
             code.push_str(&format!("/// {}\n", desc));
@@ -80,83 +551,116 @@ impl LangGenerator for DartGenerator {
         code.push_str("enum ");
         code.push_str(&enumm.id);
         code.push_str(" {");
-        let is_complex = enumm
-            .variants
-            .iter()
-            .any(|variant| matches!(variant, EnumKind::Complex(_)));
-        for variant in &enumm.variants {
-            //TODO!: Handle default value
-            let is_last = variant == enumm.variants.last().unwrap();
-            match variant {
-                EnumKind::Simple(name) => {
-                    code.push_str(&format!("{},", ccase!(camel, name)));
-                }
-                EnumKind::Complex(variant) => {
-                    if let Some(desc) = variant.description.as_ref() {
-                        code.push_str(&format!("/// {}\n", desc));
-                    }
-                    code.push_str(&format!("{}", ccase!(camel, variant.id.as_str())));
-
-                    match &variant.value {
-                        EnumVariantValue::Single(str) => {
-                            code.push_str(&format!("(\"{str}\")"));
-                        }
-                        EnumVariantValue::Multiple(_map) => {}
-                    }
-
-                    if is_last {
-                        code.push_str(";");
-                    } else {
-                        code.push_str(",");
-                    }
-                }
+        // Every variant carries its JSON value, which is the variant id unless a
+        // single `value:` is given
+        for (i, variant) in enumm.variants.iter().enumerate() {
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("/// {}\n", desc));
             }
+            code.push_str(&format!(
+                "{}(\"{}\"){}",
+                dart_name(variant.id()),
+                variant
+                    .wire_value()
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('$', "\\$"),
+                if i + 1 == enumm.variants.len() {
+                    ';'
+                } else {
+                    ','
+                }
+            ));
         }
 
-        if is_complex {
-            code.push_str("final String value;\n\n");
-            code.push_str(&format!("const {}(this.value);", enumm.id));
-        }
+        code.push_str("final String value;\n\n");
+        code.push_str(&format!("const {}(this.value);\n\n", enumm.id));
+        code.push_str(&format!(
+            "static {} fromValue(String value) => values.firstWhere((e) => e.value == value);",
+            enumm.id
+        ));
 
-        code.push_str("}");
-        code
+        code.push('}');
+        (code, BTreeSet::new())
     }
 
-    fn gen_class(&self, class: &Class, error_enum: Option<&Enum>) -> (String, HashSet<String>) {
-        let mut imports = HashSet::new();
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
         let mut code = String::new();
         if let Some(desc) = &class.description {
             code.push_str(&format!("/// {}\n", desc));
         }
+        if class.immutable == Some(true) {
+            imports.insert("import 'package:meta/meta.dart';\n".to_string());
+            code.push_str("@immutable\n");
+        }
         code.push_str(&format!("class {} {{", class.id));
-        let mut need_priv_constructor = false;
-        let mut props = HashMap::new();
-        let mut pub_props = HashMap::new();
-        let mut priv_props = HashMap::new();
-        let mut props_meta = HashMap::<String, (String, bool, bool)>::new();
-        for (name, ty) in &class.properties {
+        let need_priv_constructor = is_validated(class);
+        let mut props = IndexMap::new();
+        let mut pub_props = IndexMap::new();
+        let mut priv_props = IndexMap::new();
+        let mut props_meta = IndexMap::<String, (String, bool, bool)>::new();
+        let mut json_fields = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            primitive_imports(&ty, &IMPORTS, &mut imports);
+            let default = udl
+                .property_default(prop, &ty)
+                .map(|default| dart_default(&default, &ty));
+            // Optional fields fall back to the zero value of their type, or to null
+            // when it has none
+            let (ty, default) = match default {
+                None if ty.optional && !ty.nullable => match dart_zero_value(&ty) {
+                    Some(zero) => (ty, Some(zero)),
+                    None => (
+                        TypeExpr {
+                            nullable: true,
+                            ..ty
+                        },
+                        None,
+                    ),
+                },
+                default => (ty, default),
+            };
+            json_fields.push((
+                dart_name(name),
+                ccase!(snake, name),
+                ty.clone(),
+                default.clone(),
+            ));
+            let required = !ty.optional && default.is_none();
             let mut private = false;
-            #[allow(unused)]
-            let mut type_str = String::new();
-            match ty {
-                Property::Type(ty) => {
-                    let (type_, suffix) = process_type(ty);
-                    let name = ccase!(camel, name);
-                    code.push_str(&format!("final {}{} {};", type_, suffix, name));
-                    type_str = format!("{}{}", type_, suffix);
-                    props_meta.insert(name, (type_, !suffix.is_empty(), false));
+            let type_str = dart_type(&ty);
+            match prop {
+                Property::Type(_) => {
+                    let name = dart_name(name);
+                    code.push_str(&format!("final {} {};", type_str, name));
+                    props_meta.insert(name, (dart_base_type(&ty), ty.nullable, false));
                 }
                 Property::Map(map) => {
-                    let name = ccase!(camel, name);
+                    let name = dart_name(name);
                     if let Some(desc) = &map.get(&PropertyKey::Description) {
                         code.push_str(&format!("/// {}\n", desc));
                     }
                     private = map.get(&PropertyKey::Private) == Some(&String::from("true"));
-                    need_priv_constructor =
-                        map.keys().any(|k| VALIDATORS.contains(&k)) && class.error.is_some();
-                    let (ty, suffix) = process_type(&map[&PropertyKey::Type]);
-                    type_str = format!("{}{}", ty, suffix);
-                    props_meta.insert(name.clone(), (ty, !suffix.is_empty(), private));
+                    props_meta.insert(name.clone(), (dart_base_type(&ty), ty.nullable, private));
                     code.push_str(&format!(
                         "final {} {}{};",
                         type_str,
@@ -168,16 +672,19 @@ impl LangGenerator for DartGenerator {
                     }
                 }
             }
-            let prefix = if !private {
-                "required this."
+            let name = dart_name(name);
+            let target = if private {
+                format!("{} {}", type_str, name)
             } else {
-                &format!("{} ", type_str)
+                format!("this.{}", name)
             };
-            props.insert(
-                format!("{}{}", prefix, ccase!(camel, name)),
-                type_str.clone(),
-            );
-            pub_props.insert(ccase!(camel, name), type_str);
+            let param = match &default {
+                Some(default) => format!("{} = {}", target, default),
+                None if required => format!("required {}", target),
+                None => target,
+            };
+            props.insert(param, type_str.clone());
+            pub_props.insert(name, (type_str, default, required));
         }
         code.push_str("\n\n");
         let priv_props_list = priv_props
@@ -193,136 +700,160 @@ impl LangGenerator for DartGenerator {
             "    const {}{}({{ {} }}){};",
             class.id,
             if need_priv_constructor { "._" } else { "" },
-            props
-                .keys()
-                .into_iter()
-                .cloned()
-                .collect::<Vec<String>>()
-                .join(", "),
+            props.keys().cloned().collect::<Vec<String>>().join(", "),
             suffix
         ));
 
         code.push_str("\n\n");
 
+        // Wire names are snake_case, missing values fall back to the field default
+        code.push_str(&format!(
+            "    factory {}.fromJson(Map<String, dynamic> json) => {}{}({});\n\n",
+            class.id,
+            class.id,
+            if need_priv_constructor { "._" } else { "" },
+            json_fields
+                .iter()
+                .map(|(name, wire, ty, default)| {
+                    let value = format!("json['{}']", wire);
+                    match default {
+                        // An explicit null stays null, only a missing key defaults
+                        Some(default) if ty.nullable => format!(
+                            "{}: json.containsKey('{}') ? {} : {}",
+                            name,
+                            wire,
+                            dart_from_json(udl, &value, ty),
+                            default
+                        ),
+                        Some(default) => {
+                            let ty = TypeExpr {
+                                nullable: false,
+                                ..ty.clone()
+                            };
+                            let decoded = dart_from_json(udl, &value, &ty);
+                            format!("{}: {} == null ? {} : {}", name, value, default, decoded)
+                        }
+                        None => format!("{}: {}", name, dart_from_json(udl, &value, ty)),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        code.push_str(&format!(
+            "    Map<String, dynamic> toJson() => {{ {} }};\n\n",
+            json_fields
+                .iter()
+                .map(|(name, wire, ty, _)| format!("'{}': {}", wire, dart_to_json(udl, name, ty)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for (_, _, ty, _) in &json_fields {
+            ty.visit(&mut |ty| {
+                if ty.kind == TypeKind::Primitive("bytes".to_string()) {
+                    imports.insert("import 'dart:convert';\n".to_string());
+                }
+            });
+        }
+
         if need_priv_constructor {
-            if error_enum.is_none() {
-                println!("{}", class.id);
-                panic!("Error enum is required for private constructor");
-            }
-            let err_enum_name = error_enum.unwrap().id.clone();
+            let Some(error_enum) = error_enum else {
+                return Err(UdlError::MissingErrorEnum {
+                    model: class.id.clone(),
+                    error: class.error.clone().unwrap_or_default(),
+                });
+            };
+            let err_enum_name = error_enum.id.clone();
             code.push_str(&format!(
                 "    static ResultDart<{}, {}> build({{ {} }}) {{",
                 class.id,
                 class.error.clone().unwrap_or_default(),
                 pub_props
                     .iter()
-                    .map(|(name, ty)| format!("required {} {}", ty, name))
+                    .map(|(name, (ty, default, required))| match default {
+                        Some(default) => format!("{} {} = {}", ty, name, default),
+                        None if *required => format!("required {} {}", ty, name),
+                        None => format!("{} {}", ty, name),
+                    })
                     .collect::<Vec<String>>()
                     .join(", "),
             ));
             imports.insert("import 'package:result_dart/result_dart.dart';\n".to_string());
 
+            let constraints = constraints(class, error_enum)?;
             for (name, prop) in &class.properties {
-                if let Property::Map(map) = prop {
-                    for (key, value) in map {
-                        if key == &PropertyKey::Default {
-                            continue;
+                // Validators no error variant maps to are not checked, so they get
+                // no comment either
+                let checked = |key: &PropertyKey| {
+                    constraints
+                        .iter()
+                        .filter(|c| c.property == name)
+                        .any(|c| match c.check {
+                            Check::Min(_) | Check::Max(_) => matches!(
+                                key,
+                                PropertyKey::Limit | PropertyKey::Min | PropertyKey::Max
+                            ),
+                            Check::Format(_) => key == &PropertyKey::Format,
+                            Check::Pattern(_) => key == &PropertyKey::Pattern,
+                        })
+                };
+                if cfg!(debug_assertions)
+                    && let Property::Map(map) = prop
+                {
+                    for key in map.keys() {
+                        if VALIDATORS.contains(key) && checked(key) {
+                            code.push_str(&format!("// {:?} Validator found for {}\n", key, name));
                         }
-                        if VALIDATORS.contains(&key) {
-                            if key == &PropertyKey::Default {
-                                #[cfg(debug_assertions)]
-                                {
-                                    code.push_str(&format!(
-                                        "// {:?} Validator found for {}\n",
-                                        key, name
-                                    ));
-                                }
-                                continue;
-                            }
-                            #[cfg(debug_assertions)]
-                            {
-                                code.push_str(&format!(
-                                    "// {:?} Validator found for {}\n",
-                                    key, name
-                                ));
-                            }
-                            let (min, max, def) = if key == &PropertyKey::Limit {
-                                parse_limit_validator(value)
-                            } else if key == &PropertyKey::Min {
-                                (value.parse().unwrap(), -1, -1)
-                            } else if key == &PropertyKey::Max {
-                                (0, value.parse().unwrap(), -1)
+                    }
+                }
+                for constraint in constraints.iter().filter(|c| c.property == name) {
+                    let name = dart_name(name);
+                    let (type_, nullable, _) = props_meta.get(&name).unwrap();
+                    // A missing value passes the check
+                    let condition = match constraint.check {
+                        Check::Min(limit) | Check::Max(limit) => {
+                            let operator = match constraint.check {
+                                Check::Min(_) => "<",
+                                _ => ">",
+                            };
+                            // Strings are measured in code points, not UTF-16 units
+                            let length = if type_ == "String" {
+                                "runes.length"
                             } else {
-                                (-1, -1, -1)
+                                "length"
                             };
-                            if (min, max, def) == (-1, -1, -1) {
-                                continue;
-                            }
-                            let mut completed = (min == -1, max == -1);
-                            loop {
-                                if completed == (true, true) {
-                                    break;
-                                }
-                                let is_min_variant;
-                                let (filter_name, operator, value) = if !completed.0 && min != -1 {
-                                    completed.0 = true;
-                                    is_min_variant = true;
-                                    ("limit:min", "<", min)
-                                } else {
-                                    is_min_variant = false;
-                                    completed.1 = true;
-                                    ("limit:max", ">", max)
-                                };
-                                let variant =
-                                    extract_enum_variant(error_enum.unwrap(), filter_name);
-                                let variant = if variant.len() == 1 {
-                                    let v = variant.get(0).unwrap();
-                                    Some((&v.0, &v.1))
-                                } else {
-                                    variant
-                                        .iter()
-                                        .filter_map(|(id, str, field)| {
-                                            if let Some(name_) = field
-                                                && name_ == name
-                                            {
-                                                Some((id, str))
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                        .next()
-                                };
-                                let prop_suffix = {
-                                    let name_str = ccase!(camel, name);
-                                    let (type_, nullable, _) = props_meta.get(&name_str).unwrap();
-                                    let is_number = NUMBER_TYPES.contains(&type_.as_str());
-                                    let name = ccase!(camel, name);
-                                    if is_number {
-                                        name
-                                    } else {
-                                        if *nullable {
-                                            let suffix = if is_min_variant { min } else { max };
-                                            format!("({}?.length ?? {})", name, suffix)
-                                        } else {
-                                            format!("{}.length", name)
-                                        }
-                                    }
-                                };
-                                if let Some((variant, _)) = variant {
-                                    code.push_str(&format!(
-                                        "if ({} {} {}) {{\n",
-                                        prop_suffix, operator, value
-                                    ));
-                                    code.push_str(&format!(
-                                        "    return Failure({}.{});\n",
-                                        err_enum_name,
-                                        ccase!(camel, variant)
-                                    ));
-                                    code.push_str("}\n");
-                                }
+                            let value = match (NUMBER_TYPES.contains(&type_.as_str()), *nullable) {
+                                (true, false) => name,
+                                (true, true) => format!("({} ?? {})", name, limit),
+                                (false, true) => format!("({}?.{} ?? {})", name, length, limit),
+                                (false, false) => format!("{}.{}", name, length),
+                            };
+                            format!("{} {} {}", value, operator, limit)
+                        }
+                        check => {
+                            let value = if type_ == "String" {
+                                name.clone()
+                            } else {
+                                format!("{}.toString()", name)
+                            };
+                            let matches = format!(
+                                "RegExp({}).hasMatch({})",
+                                dart_raw_string(check.pattern().unwrap_or_default()),
+                                value
+                            );
+                            if *nullable {
+                                format!("{} != null && !{}", name, matches)
+                            } else {
+                                format!("!{}", matches)
                             }
                         }
-                    }
+                    };
+                    code.push_str(&format!("if ({}) {{\n", condition));
+                    code.push_str(&format!(
+                        "    return Failure({}.{});\n",
+                        err_enum_name,
+                        dart_name(constraint.variant)
+                    ));
+                    code.push_str("}\n");
                 }
             }
 
@@ -333,27 +864,101 @@ impl LangGenerator for DartGenerator {
                     .properties
                     .keys()
                     .fold(String::new(), |acc, k| acc
-                        + &format!("{}:{},", ccase!(camel, k), ccase!(camel, k)))
+                        + &format!("{}:{},", dart_name(k), dart_name(k)))
                     .trim_end_matches(", ")
             ));
 
-            code.push_str("}");
+            code.push('}');
         }
 
         for (name, ty) in priv_props {
             code.push_str("\n\n");
             code.push_str(&format!("    {} get {} => _{};", ty, name, name));
         }
+        code.push_str("\n\n");
+        let fields = json_fields
+            .iter()
+            .map(|(name, _, ty, _)| {
+                let field = match props_meta.get(name) {
+                    Some((_, _, true)) => format!("_{}", name),
+                    _ => name.clone(),
+                };
+                (name.as_str(), field, ty)
+            })
+            .collect::<Vec<_>>();
+        code.push_str(&dart_value_methods(class, &fields, &mut imports));
 
-        code.push_str(&format!("}}\n"));
-        (code, imports)
+        code.push_str("}\n");
+        Ok((code, imports))
     }
 
-    fn generate(&self, udl: &UDL) -> String {
-        let mut imports = HashSet::new();
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        code.push_str(&format!("abstract interface class {} {{\n", interface.id));
+        for (i, method) in interface.methods.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            for (_, ty) in &params {
+                primitive_imports(ty, &IMPORTS, &mut imports);
+            }
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => "void".to_string(),
+                Some(ReturnType::Type(ty)) => {
+                    primitive_imports(&ty, &IMPORTS, &mut imports);
+                    dart_type(&ty)
+                }
+                Some(ReturnType::Result { ok, err }) => {
+                    imports.insert("import 'package:result_dart/result_dart.dart';\n".to_string());
+                    for ty in ok.iter().chain([&err]) {
+                        primitive_imports(ty, &IMPORTS, &mut imports);
+                    }
+                    let ok = ok.as_ref().map_or("Unit".to_string(), dart_type);
+                    format!("ResultDart<{}, {}>", ok, dart_type(&err))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            let returns = if method.is_async {
+                format!("Future<{}>", returns)
+            } else {
+                returns
+            };
+            code.push_str(&format!(
+                "    {} {}({});\n",
+                returns,
+                dart_name(&method.id),
+                method_params(&params)
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, imports))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut imports = BTreeSet::new();
         let mut code = String::new();
         for enum_def in &udl.enums {
-            code.push_str(&self.gen_enum(enum_def));
+            code.push_str(&self.gen_enum(udl, enum_def).0);
             code.push_str("\n\n");
         }
 
@@ -362,27 +967,455 @@ impl LangGenerator for DartGenerator {
                 .enums
                 .iter()
                 .find(|e| e.id == class.clone().error.unwrap_or_default());
-            let (gen_code, imports_) = self.gen_class(class, error_enum);
+            let (gen_code, imports_) = self.gen_class(udl, class, error_enum)?;
+            code.push_str(&gen_code);
+            code.push_str("\n\n");
+            imports.extend(imports_);
+        }
+
+        for interface in &udl.interfaces {
+            let (gen_code, imports_) = self.gen_interface(interface)?;
             code.push_str(&gen_code);
             code.push_str("\n\n");
             imports.extend(imports_);
         }
 
+        // Sentinel of the nullable `copyWith` parameters
+        if code.contains("= _undefined") {
+            code.insert_str(0, "const _undefined = Object();\n\n");
+        }
+
         let final_str = format!(
             "{}\n\n{}",
             imports.into_iter().collect::<Vec<_>>().join("\n"),
             code
         );
 
-        final_str
+        Ok(final_str)
     }
 
-    fn format(&self, _path: &str) {
+    fn format(&self, _path: &str) -> Result<(), UdlError> {
         // TODO!: Implement formatting logic for Dart code
         // std::process::Command::new("dart format")
         //     .arg(path)
         //     .stdout(Stdio::piped())
         //     .spawn()
         //     .expect("");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let generate = || {
+            let udl: UDL = serde_yaml::from_str(&source).unwrap();
+            DartGenerator().generate(&udl).unwrap()
+        };
+        let code = generate();
+        for _ in 0..5 {
+            assert_eq!(generate(), code);
+        }
+
+        // Fields and constructor parameters follow declaration order
+        let positions = [
+            "final String email;",
+            "final String password;",
+            "final bool rememberMe;",
+            "required this.email, required this.password, this.rememberMe = false",
+        ]
+        .iter()
+        .map(|needle| code.find(needle).unwrap())
+        .collect::<Vec<_>>();
+        assert!(positions.is_sorted(), "{:?}", positions);
+    }
+
+    #[test]
+    fn test_dart_type() {
+        let dart = |value| dart_type(&parse_type(value).unwrap());
+        assert_eq!(dart("float64?"), "double?");
+        assert_eq!(dart("List<string?>"), "List<String?>");
+        assert_eq!(
+            dart("Map<string, List<$enum::InvoiceStatus>>?^"),
+            "Map<String, List<InvoiceStatus>>?"
+        );
+        assert_eq!(dart("Map<uuid, url>"), "Map<String, Uri>");
+
+        let mut imports = BTreeSet::new();
+        primitive_imports(
+            &parse_type("List<Map<bytes, decimal?>>").unwrap(),
+            &IMPORTS,
+            &mut imports,
+        );
+        assert_eq!(
+            imports.into_iter().collect::<Vec<_>>(),
+            vec![
+                "import 'dart:typed_data';\n",
+                "import 'package:decimal/decimal.dart';\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let from = |value| dart_from_json(&udl, "json['a']", &parse_type(value).unwrap());
+        let to = |value| dart_to_json(&udl, "a", &parse_type(value).unwrap());
+        assert_eq!(from("string?"), "json['a'] as String?");
+        assert_eq!(from("uint8"), "(json['a'] as num).toInt()");
+        assert_eq!(
+            from("datetime?"),
+            "json['a'] == null ? null : DateTime.parse(json['a'] as String)"
+        );
+        assert_eq!(
+            from("List<$enum::Status>"),
+            "(json['a'] as List<dynamic>).map((e) => Status.fromValue(e as String)).toList()"
+        );
+        assert_eq!(
+            from("Map<int, Set<User?>>"),
+            "(json['a'] as Map<String, dynamic>).map((k, v) => MapEntry(int.parse(k), \
+             (v as List<dynamic>).map((e) => e == null ? null : \
+             User.fromJson(e as Map<String, dynamic>)).toSet()))"
+        );
+
+        assert_eq!(to("List<string>"), "a");
+        assert_eq!(to("Set<string>?"), "a?.toList()");
+        assert_eq!(to("datetime?"), "a?.toUtc().toIso8601String()");
+        assert_eq!(to("date"), "a.toIso8601String().substring(0, 10)");
+        assert_eq!(to("List<User?>"), "a.map((e) => e?.toJson()).toList()");
+        assert_eq!(
+            to("Map<$enum::Status, duration>?"),
+            "a?.map((k, v) => MapEntry(k.value, v.inMilliseconds))"
+        );
+        assert_eq!(to("bytes?"), "a == null ? null : base64Encode(a!)");
+    }
+
+    #[test]
+    fn test_tagged_enum() {
+        let source = std::fs::read_to_string("tests/generated_rust/collections.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let code = DartGenerator().generate(&udl).unwrap();
+        for expected in [
+            "sealed class Payment {",
+            "factory Payment.fromJson(Map<String, dynamic> json) => switch (json['type']) { \
+             'K_NONE' => PaymentKNone.fromJson(json), 'card' => PaymentKCard.fromJson(json), \
+             'K_BANK' => PaymentKBank.fromJson(json), \
+             final type => throw ArgumentError.value(type, 'type'), };",
+            "class PaymentKCard extends Payment {",
+            "Map<String, dynamic> toJson() => { 'type': 'card' };",
+            "const PaymentKBank({ required this.accountNumber, required this.paidAt });",
+            "payment: Payment.fromJson(json['payment'] as Map<String, dynamic>)",
+            "'refunds': refunds.map((e) => e.toJson()).toList()",
+        ] {
+            assert!(code.contains(expected), "{}", code);
+        }
+
+        // Every object of the JSON the generated Rust round-trips in
+        // `tests/generated_rust` is decoded by the variant class with its `type`, which
+        // reads and writes the same keys
+        let receipts: serde_yaml::Value = serde_yaml::from_str(
+            &std::fs::read_to_string("tests/generated_rust/receipts.json").unwrap(),
+        )
+        .unwrap();
+        let payments = receipts
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .flat_map(|receipt| {
+                let refunds = receipt["refunds"].as_sequence().unwrap();
+                std::iter::once(&receipt["payment"]).chain(refunds)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(payments.len(), 5);
+        for payment in payments {
+            let payment = payment.as_mapping().unwrap();
+            let tag = payment[&"type".into()].as_str().unwrap();
+            let class = code
+                .split("\nclass ")
+                .find(|class| class.contains(&format!("'type': '{}'", tag)))
+                .unwrap();
+            let name = class.split(' ').next().unwrap();
+            assert!(code.contains(&format!("'{}' => {}.fromJson(json)", tag, name)));
+            for key in payment.iter().filter_map(|(key, _)| key.as_str()) {
+                if key != "type" {
+                    assert!(class.contains(&format!("json['{}']", key)), "{}", class);
+                    assert!(class.contains(&format!("'{}': ", key)), "{}", class);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gen_json() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let code = DartGenerator().generate(&udl).unwrap();
+        for expected in [
+            // Simple variants use their id as value
+            "kAsc(\"K_ASC\"),",
+            "static SortOrder fromValue(String value) => \
+             values.firstWhere((e) => e.value == value);",
+            // Validated models go through the private constructor
+            "factory LoginRequest.fromJson(Map<String, dynamic> json) => LoginRequest._(\
+             email: json['email'] as String, password: json['password'] as String, \
+             rememberMe: json['remember_me'] == null ? false : json['remember_me'] as bool);",
+            "Map<String, dynamic> toJson() => { 'email': email, 'password': password, \
+             'remember_me': rememberMe };",
+            // Nullable fields without a default of their own default to null
+            "status: json['status'] == null ? null : \
+             InvoiceStatus.fromValue(json['status'] as String),",
+        ] {
+            assert!(code.contains(expected), "missing `{}`", expected);
+        }
+    }
+
+    #[test]
+    fn test_value_methods() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let mut udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |udl: &UDL, id: &str| udl.models.iter().find(|c| c.id == id).unwrap().clone();
+
+        let (code, imports) = DartGenerator()
+            .gen_class(
+                &udl,
+                &find(&udl, "CreateInvoiceRequest"),
+                Some(&udl.enums[2]),
+            )
+            .unwrap();
+        for expected in [
+            // Validated models are copied through `build()`
+            "ResultDart<CreateInvoiceRequest, CreateInvoiceRequestError> copyWith({ \
+             String? clientId, ",
+            "Object? notes = _undefined, bool? taxable, int? taxRate }) => build(",
+            "notes: identical(notes, _undefined) ? _notes : notes as String?, \
+             taxable: taxable ?? this.taxable,",
+            "const DeepCollectionEquality().equals(items, other.items) && _notes == other._notes",
+            "int get hashCode => Object.hashAll([clientId, invoiceNumber, issueDate, dueDate, \
+             tag, const DeepCollectionEquality().hash(items), _notes, taxable, taxRate]);",
+            "String toString() => 'CreateInvoiceRequest(clientId: $clientId, ",
+            "notes: $_notes, taxable: $taxable, taxRate: $taxRate)';",
+        ] {
+            assert!(
+                code.contains(expected),
+                "missing `{}` in {}",
+                expected,
+                code
+            );
+        }
+        assert!(imports.contains("import 'package:collection/collection.dart';\n"));
+        assert!(!code.contains("@immutable"));
+
+        let mut class = find(&udl, "FetchInvoicesRequest");
+        class.immutable = Some(true);
+        let (code, imports) = DartGenerator().gen_class(&udl, &class, None).unwrap();
+        assert!(code.starts_with(
+            "/// Request to fetch invoices\n@immutable\nclass FetchInvoicesRequest {"
+        ));
+        assert!(code.contains(
+            "FetchInvoicesRequest copyWith({ Object? status = _undefined, int? limit, \
+             int? offset, SortBy? sortBy, SortOrder? sortOrder }) => FetchInvoicesRequest("
+        ));
+        assert!(imports.contains("import 'package:meta/meta.dart';\n"));
+        assert!(!imports.contains("import 'package:collection/collection.dart';\n"));
+
+        // The sentinel is declared once per file
+        udl.models.retain(|c| c.id == "InvoiceItem");
+        let code = DartGenerator().generate(&udl).unwrap();
+        assert!(!code.contains("_undefined"));
+        udl.models.push(class);
+        let code = DartGenerator().generate(&udl).unwrap();
+        assert_eq!(code.matches("const _undefined = Object();").count(), 1);
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let mut udl: UDL = serde_yaml::from_str(&source).unwrap();
+        udl.models.truncate(1);
+        let class = &mut udl.models[0];
+        class.error = None;
+        class.properties = serde_yaml::from_str(
+            r#"
+            id: string
+            phone: string?
+            tag: string^
+            count: int?^
+            tags: List<string>^
+            owner: User^
+            "#,
+        )
+        .unwrap();
+        let (code, _) = DartGenerator()
+            .gen_class(&udl, &udl.models[0], None)
+            .unwrap();
+        assert!(code.contains("final User? owner;"), "{}", code);
+        assert!(
+            code.contains(
+                "const LoginRequest({ required this.id, required this.phone, this.tag = '', \
+                 this.count, this.tags = const [], this.owner });"
+            ),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_keywords() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Lesson
+            properties:
+              class: string
+              in: int?
+            "#,
+        )
+        .unwrap();
+        let (code, _) = DartGenerator().gen_class(&udl, &class, None).unwrap();
+        for expected in [
+            "final String class$;final int? in$;",
+            "const Lesson({ required this.class$, required this.in$ });",
+            "Lesson(class$: json['class'] as String, in$: json['in'] == null ? null : ",
+            "'class': class$, 'in': in$",
+            "class$ == other.class$ && in$ == other.in$",
+            "String toString() => 'Lesson(class: ${class$}, in: ${in$})';",
+        ] {
+            assert!(
+                code.contains(expected),
+                "missing `{}` in {}",
+                expected,
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_constructor_defaults() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let code = DartGenerator().generate(&udl).unwrap();
+        for expected in [
+            "this.rememberMe = false",
+            "bool rememberMe = false",
+            "this.status,",
+            // Property level and `limit:` defaults prevail over type defaults
+            "this.taxable = true",
+            "this.limit = 20",
+            "this.offset = 0",
+            "this.sortBy = SortBy.kCreatedAt",
+            "this.sortOrder = SortOrder.kDesc",
+        ] {
+            assert!(code.contains(expected), "missing `{}`", expected);
+        }
+    }
+
+    #[test]
+    fn test_length_validators() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let code = DartGenerator().generate(&udl).unwrap();
+        // Strings are measured in code points, so an emoji counts once
+        assert!(code.contains("if (password.runes.length < 8) {"));
+        assert!(code.contains("if ((notes?.runes.length ?? 500) > 500) {"));
+    }
+
+    #[test]
+    fn test_format_validators() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let mut udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let code = DartGenerator().generate(&udl).unwrap();
+        assert!(code.contains(
+            "if (!RegExp(r'^[^@\\s]+@[^@\\s]+\\.[^@\\s]+$').hasMatch(email)) {\n\
+             \x20   return Failure(LoginError.kInvalidEmail);\n}"
+        ));
+        // `User.id` and `User.email` have formats no `UserNameError` variant targets
+        assert!(!code.contains("Validator found for id\n"));
+        assert_eq!(
+            code.matches("Format Validator found for email\n").count(),
+            1
+        );
+
+        let class = &mut udl.models[0];
+        class.properties = serde_yaml::from_str(
+            r#"
+            email:
+              type: string?
+              pattern: "^[a-z'\"]+$"
+            "#,
+        )
+        .unwrap();
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: LoginError
+            variants:
+              - id: K_NO_MATCH
+                value: "No match"
+                target: "pattern"
+            "#,
+        )
+        .unwrap();
+        let (code, _) = DartGenerator()
+            .gen_class(&udl, &udl.models[0], Some(&enumm))
+            .unwrap();
+        assert!(
+            code.contains(
+                "if (email != null && !RegExp('^[a-z\\'\"]+\\$').hasMatch(email)) {\n\
+                 \x20   return Failure(LoginError.kNoMatch);\n}"
+            ),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetch_invoices
+                params: FetchInvoicesRequest
+                returns: Result<List<Invoice>, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  include_items: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = DartGenerator().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "/// Invoice management operations\n\
+             abstract interface class InvoiceService {\n\
+             \x20   Future<ResultDart<List<Invoice>, InvoiceStatus>> fetchInvoices(FetchInvoicesRequest fetchInvoicesRequest);\n\
+             \n\
+             \x20   /// Get a specific invoice by ID\n\
+             \x20   Invoice? getInvoice({ required String id, bool? includeItems });\n\
+             \n\
+             \x20   Future<ResultDart<Unit, ApiError>> deleteInvoice(String value);\n\
+             \n\
+             \x20   void ping();\n\
+             }\n"
+        );
+        assert_eq!(
+            imports,
+            BTreeSet::from(["import 'package:result_dart/result_dart.dart';\n".to_string()])
+        );
     }
 }
@@ -0,0 +1,141 @@
+use std::collections::BTreeSet;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, Property, PropertyKey},
+    enums::{Enum, EnumKind, EnumVariantValue},
+};
+
+pub(crate) struct DocsGenerator();
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+impl LangGenerator for DocsGenerator {
+    fn extension(&self) -> &str {
+        "md"
+    }
+
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut code = format!("### {}\n\n", enumm.id);
+        if let Some(desc) = &enumm.description {
+            code.push_str(&format!("{}\n\n", desc));
+        }
+        if let Some(variant) = udl.enum_default(enumm) {
+            code.push_str(&format!("Default: `{}`\n\n", variant.id()));
+        }
+        code.push_str("| Variant | Value | Description |\n");
+        code.push_str("|---|---|---|\n");
+        for variant in &enumm.variants {
+            match variant {
+                EnumKind::Simple(name) => {
+                    code.push_str(&format!("| `{}` | | |\n", name));
+                }
+                EnumKind::Complex(variant) => {
+                    let value = match &variant.value {
+                        EnumVariantValue::Single(str) => escape_cell(str),
+                        EnumVariantValue::Multiple(map) => map
+                            .iter()
+                            .map(|(key, ty)| format!("`{}: {}`", key, ty))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    };
+                    code.push_str(&format!(
+                        "| `{}` | {} | {} |\n",
+                        variant.id,
+                        value,
+                        escape_cell(variant.description.as_deref().unwrap_or_default())
+                    ));
+                }
+            }
+        }
+        (code, BTreeSet::new())
+    }
+
+    fn gen_class(
+        &self,
+        _udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut code = format!("### {}\n\n", class.id);
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("{}\n\n", desc));
+        }
+        if let Some(error_enum) = error_enum {
+            code.push_str(&format!(
+                "Errors: [`{}`](#{})\n\n",
+                error_enum.id,
+                error_enum.id.to_lowercase()
+            ));
+        }
+        code.push_str("| Property | Type | Description | Constraints |\n");
+        code.push_str("|---|---|---|---|\n");
+        for (name, prop) in &class.properties {
+            match prop {
+                Property::Type(ty) => {
+                    code.push_str(&format!("| `{}` | `{}` | | |\n", name, ty));
+                }
+                Property::Map(map) => {
+                    let constraints = map
+                        .iter()
+                        .filter(|(key, _)| {
+                            !matches!(key, PropertyKey::Type | PropertyKey::Description)
+                        })
+                        .map(|(key, value)| {
+                            format!("{}: `{}`", format!("{:?}", key).to_lowercase(), value)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    code.push_str(&format!(
+                        "| `{}` | `{}` | {} | {} |\n",
+                        name,
+                        map.get(&PropertyKey::Type).map_or("", |ty| ty.as_str()),
+                        escape_cell(
+                            map.get(&PropertyKey::Description)
+                                .map_or("", |desc| desc.as_str())
+                        ),
+                        escape_cell(&constraints)
+                    ));
+                }
+            }
+        }
+        Ok((code, BTreeSet::new()))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut code = format!("# {}\n\n", udl.project.name);
+        code.push_str(&format!("{}\n\n", udl.project.description));
+        code.push_str(&format!(
+            "Version `{}` · Namespace `{}` · License {}\n\n",
+            udl.project.version, udl.project.namespace, udl.project.license
+        ));
+
+        if !udl.enums.is_empty() {
+            code.push_str("## Enums\n\n");
+            for enum_def in &udl.enums {
+                code.push_str(&self.gen_enum(udl, enum_def).0);
+                code.push('\n');
+            }
+        }
+
+        if !udl.models.is_empty() {
+            code.push_str("## Models\n\n");
+            for class in &udl.models {
+                let error_enum = udl
+                    .enums
+                    .iter()
+                    .find(|e| Some(&e.id) == class.error.as_ref());
+                code.push_str(&self.gen_class(udl, class, error_enum)?.0);
+                code.push('\n');
+            }
+        }
+
+        Ok(code)
+    }
+
+    fn format(&self, _path: &str) -> Result<(), UdlError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,882 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use convert_case::ccase;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::{parse_type, primitive_imports},
+};
+
+pub(crate) struct GoGenerator();
+
+/// `encoding/json` keeps the wire format of the Dart and Rust targets: `[]byte` is
+/// base64, decimals are strings and durations milliseconds. Sets have no Go type
+/// and are slices.
+static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("bool", "bool"),
+        ("int", "int"),
+        ("float", "float64"),
+        ("string", "string"),
+        ("isize", "int"),
+        ("int8", "int8"),
+        ("int16", "int16"),
+        ("int32", "int32"),
+        ("int64", "int64"),
+        ("usize", "uint"),
+        ("uint8", "uint8"),
+        ("uint16", "uint16"),
+        ("uint32", "uint32"),
+        ("uint64", "uint64"),
+        ("float32", "float32"),
+        ("float64", "float64"),
+        ("datetime", "time.Time"),
+        ("date", "string"),
+        ("object", "any"),
+        ("uuid", "string"),
+        ("decimal", "string"),
+        ("bytes", "[]byte"),
+        ("url", "string"),
+        ("duration", "int64"),
+        ("email", "string"),
+    ])
+});
+
+/// Packages of the primitives that need one.
+static IMPORTS: LazyLock<HashMap<&str, &str>> =
+    std::sync::LazyLock::new(|| HashMap::from([("datetime", "time")]));
+
+/// Go type without the pointer of nullable values.
+fn go_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "struct{}".to_string(),
+        TypeKind::List(item) | TypeKind::Set(item) => format!("[]{}", go_type(item)),
+        TypeKind::Map(key, value) => format!("map[{}]{}", go_type(key), go_type(value)),
+        TypeKind::Generic { name, args } => format!(
+            "{}[{}]",
+            name,
+            args.iter().map(go_type).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Whether values of `ty` can already be nil, so nullable ones need no pointer.
+fn is_nilable(ty: &TypeExpr) -> bool {
+    matches!(
+        ty.kind,
+        TypeKind::List(_) | TypeKind::Set(_) | TypeKind::Map(..)
+    ) || matches!(go_base_type(ty).as_str(), "any" | "[]byte")
+}
+
+/// Whether values of `ty` are structs, which `omitempty` never leaves out: times,
+/// models and tagged enums. Fields that may be missing point to them instead.
+fn is_struct(udl: &UDL, ty: &TypeExpr) -> bool {
+    match &ty.kind {
+        TypeKind::Primitive(name) => name == "datetime",
+        TypeKind::Model(_) => true,
+        TypeKind::Enum(id) => udl.enums.iter().any(|e| &e.id == id && e.is_tagged()),
+        _ => false,
+    }
+}
+
+fn go_type(ty: &TypeExpr) -> String {
+    if ty.nullable && !is_nilable(ty) {
+        format!("*{}", go_base_type(ty))
+    } else {
+        go_base_type(ty)
+    }
+}
+
+/// Escaped Go string literal of `value`.
+fn go_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Backquoted literal of a regex, or an interpreted one when it contains a backquote.
+fn go_regex(pattern: &str) -> String {
+    if pattern.contains('`') {
+        go_string(pattern)
+    } else {
+        format!("`{}`", pattern)
+    }
+}
+
+/// Initialisms Go spells in one case, e.g. `UserID` rather than `UserId`.
+const INITIALISMS: [&str; 38] = [
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "LHS", "QPS", "RAM", "RHS", "RPC", "SLA", "SMTP", "SQL", "SSH", "TCP", "TLS", "TTL",
+    "UDP", "UI", "UID", "UUID", "URI", "URL", "UTF8", "VM", "XML", "XMPP", "XSRF", "XSS",
+];
+
+/// PascalCase Go name of a field, method or constant, with initialisms in upper case.
+fn go_name(name: &str) -> String {
+    ccase!(snake, name)
+        .split('_')
+        .map(|word| {
+            let upper = word.to_ascii_uppercase();
+            if INITIALISMS.contains(&upper.as_str()) {
+                upper
+            } else {
+                ccase!(pascal, word)
+            }
+        })
+        .collect()
+}
+
+/// camelCase Go name of a parameter, e.g. `userID`, or `id` for `id`.
+fn go_param(name: &str) -> String {
+    let name = ccase!(snake, name);
+    let (first, rest) = name.split_once('_').unwrap_or((&name, ""));
+    format!("{}{}", first, go_name(rest))
+}
+
+/// Name of the constant of `variant` in the type `id`.
+fn const_name(id: &str, variant: &EnumKind) -> String {
+    format!("{}{}", id, go_name(variant.id()))
+}
+
+/// Whether the enum or model `id` is returned as an `error`: the `error:` of a model
+/// or the error of a service method.
+fn is_error_type(udl: &UDL, id: &str) -> bool {
+    udl.models
+        .iter()
+        .any(|class| class.error.as_deref() == Some(id))
+        || udl
+            .interfaces
+            .iter()
+            .flat_map(|interface| &interface.methods)
+            .any(|method| {
+                matches!(method.return_type(), Some(ReturnType::Result { err, .. }) if err.name() == id)
+            })
+}
+
+/// `json` tag of a field. Values that may be missing are left out when empty.
+fn json_tag(name: &str, omit_empty: bool) -> String {
+    format!(
+        "`json:\"{}{}\"`",
+        ccase!(snake, name),
+        if omit_empty { ",omitempty" } else { "" }
+    )
+}
+
+/// Parameters of a service method, which follow the `context.Context` of async
+/// methods.
+fn method_params(
+    params: &[(Option<&str>, TypeExpr)],
+    imports: &mut BTreeSet<String>,
+) -> Vec<String> {
+    params
+        .iter()
+        .map(|(name, ty)| {
+            primitive_imports(ty, &IMPORTS, imports);
+            let name = go_param(param_name(*name, ty));
+            format!("{} {}", name, go_type(ty))
+        })
+        .collect()
+}
+
+/// Name of the package level `Regexp` a format or pattern constraint of `class` is
+/// checked with.
+fn regex_name(class: &Class, constraint: &Constraint) -> String {
+    let suffix = match constraint.check {
+        Check::Pattern(_) => "Pattern",
+        _ => "Format",
+    };
+    format!(
+        "{}{}{}",
+        ccase!(camel, class.id.as_str()),
+        go_name(constraint.property),
+        suffix
+    )
+}
+
+/// Condition under which `constraint` fails for the parameter `name`. Limits compare
+/// numbers by value, strings by their rune count, which is their number of code
+/// points, and collections by `len`. Patterns match the value as a string. A nil
+/// pointer passes.
+fn constraint_failure(regex: &str, constraint: &Constraint, name: &str, ty: &TypeExpr) -> String {
+    let value = if ty.nullable && !is_nilable(ty) {
+        format!("*{}", name)
+    } else {
+        name.to_string()
+    };
+    let base = go_base_type(ty);
+    let check = match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            let value = if ty.is_number() {
+                value
+            } else if base == "string" {
+                format!("utf8.RuneCountInString({})", value)
+            } else {
+                format!("len({})", value)
+            };
+            format!("{} {} {}", value, operator, limit)
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            if base == "string" {
+                format!("!{}.MatchString({})", regex, value)
+            } else {
+                format!("!{}.MatchString(fmt.Sprint({}))", regex, value)
+            }
+        }
+    };
+    if ty.nullable {
+        format!("{} != nil && {}", name, check)
+    } else {
+        check
+    }
+}
+
+impl GoGenerator {
+    /// String type `id` with a constant per variant, which only marshals and
+    /// unmarshals its known values.
+    fn gen_string_enum(
+        &self,
+        id: &str,
+        enumm: &Enum,
+        error: bool,
+        imports: &mut BTreeSet<String>,
+    ) -> String {
+        imports.insert("encoding/json".to_string());
+        imports.insert("fmt".to_string());
+        let mut code = format!("type {} string\n\nconst (\n", id);
+        for variant in &enumm.variants {
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("\t// {}\n", desc));
+            }
+            code.push_str(&format!(
+                "\t{} {} = {}\n",
+                const_name(id, variant),
+                id,
+                go_string(variant.wire_value())
+            ));
+        }
+        let constants = enumm
+            .variants
+            .iter()
+            .map(|variant| const_name(id, variant))
+            .collect::<Vec<_>>()
+            .join(", ");
+        code.push_str(&format!(
+            ")\n\n\
+             // IsValid reports whether e is one of the {id} constants.\n\
+             func (e {id}) IsValid() bool {{\n\
+             \tswitch e {{\n\
+             \tcase {constants}:\n\
+             \t\treturn true\n\
+             \t}}\n\
+             \treturn false\n\
+             }}\n\n\
+             func (e {id}) MarshalJSON() ([]byte, error) {{\n\
+             \tif !e.IsValid() {{\n\
+             \t\treturn nil, fmt.Errorf(\"invalid {id}: %q\", string(e))\n\
+             \t}}\n\
+             \treturn json.Marshal(string(e))\n\
+             }}\n\n\
+             func (e *{id}) UnmarshalJSON(data []byte) error {{\n\
+             \tvar value string\n\
+             \tif err := json.Unmarshal(data, &value); err != nil {{\n\
+             \t\treturn err\n\
+             \t}}\n\
+             \tif !{id}(value).IsValid() {{\n\
+             \t\treturn fmt.Errorf(\"invalid {id}: %q\", value)\n\
+             \t}}\n\
+             \t*e = {id}(value)\n\
+             \treturn nil\n\
+             }}\n"
+        ));
+        if error {
+            code.push_str(&format!(
+                "\nfunc (e {}) Error() string {{\n\treturn string(e)\n}}\n",
+                id
+            ));
+        }
+        code
+    }
+}
+
+impl LangGenerator for GoGenerator {
+    fn extension(&self) -> &str {
+        "go"
+    }
+
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if let Some(desc) = &enumm.description {
+            code.push_str(&format!("// {}\n", desc));
+        }
+        let fields = enumm
+            .variants
+            .iter()
+            .filter_map(|variant| variant.fields())
+            .flatten()
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            code.push_str(&self.gen_string_enum(
+                &enumm.id,
+                enumm,
+                is_error_type(udl, &enumm.id),
+                &mut imports,
+            ));
+            return (code, imports);
+        }
+
+        // Variants with fields share one struct tagged with `type`, like the Rust
+        // enum, e.g. `{ "type": "K_PAID", "amount": 10 }`. Fields of the other
+        // variants stay empty.
+        let tag = format!("{}Type", enumm.id);
+        code.push_str(&format!(
+            "type {} struct {{\n\tType {} `json:\"type\"`\n",
+            enumm.id, tag
+        ));
+        let mut names = BTreeSet::new();
+        for (key, value) in fields {
+            if !names.insert(go_name(key)) {
+                continue;
+            }
+            let ty = match parse_type(value) {
+                Some(ty) => {
+                    primitive_imports(&ty, &IMPORTS, &mut imports);
+                    go_type(&TypeExpr {
+                        nullable: ty.nullable || is_struct(udl, &ty),
+                        ..ty
+                    })
+                }
+                None => value.clone(),
+            };
+            code.push_str(&format!(
+                "\t{} {} {}\n",
+                go_name(key),
+                ty,
+                json_tag(key, true)
+            ));
+        }
+        code.push_str(&format!(
+            "}}\n\n// {} tags the variants of {}.\n",
+            tag, enumm.id
+        ));
+        code.push_str(&self.gen_string_enum(&tag, enumm, false, &mut imports));
+        (code, imports)
+    }
+
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("// {}\n", desc));
+        }
+        code.push_str(&format!("type {} struct {{\n", class.id));
+        let mut fields = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            primitive_imports(&ty, &IMPORTS, &mut imports);
+            let ty = TypeExpr {
+                nullable: ty.nullable || (ty.optional && is_struct(udl, &ty)),
+                ..ty
+            };
+            if let Some(desc) = prop.get(&PropertyKey::Description) {
+                code.push_str(&format!("\t// {}\n", desc));
+            }
+            code.push_str(&format!(
+                "\t{} {} {}\n",
+                go_name(name),
+                go_type(&ty),
+                json_tag(name, ty.nullable || ty.optional)
+            ));
+            fields.push((name, ty));
+        }
+        code.push_str("}\n");
+
+        // Models returned as the error of a `Result` implement `error`, printing
+        // their fields through a type without the method
+        if is_error_type(udl, &class.id) {
+            imports.insert("fmt".to_string());
+            code.push_str(&format!(
+                "\nfunc (e {id}) Error() string {{\n\
+                 \ttype plain {id}\n\
+                 \treturn fmt.Sprintf(\"{id}%+v\", plain(e))\n\
+                 }}\n",
+                id = class.id
+            ));
+        }
+
+        if !is_validated(class) {
+            return Ok((code, imports));
+        }
+        let Some(error_enum) = error_enum else {
+            return Err(UdlError::MissingErrorEnum {
+                model: class.id.clone(),
+                error: class.error.clone().unwrap_or_default(),
+            });
+        };
+        let constraints = constraints(class, error_enum)?;
+        let regexes = constraints
+            .iter()
+            .filter_map(|constraint| {
+                let pattern = constraint.check.pattern()?;
+                Some(format!(
+                    "var {} = regexp.MustCompile({})\n",
+                    regex_name(class, constraint),
+                    go_regex(pattern)
+                ))
+            })
+            .collect::<String>();
+        if !regexes.is_empty() {
+            imports.insert("regexp".to_string());
+            code.push('\n');
+            code.push_str(&regexes);
+        }
+        code.push_str(&format!(
+            "\n// New{id} returns a {id} if every field is valid, or else the {error} of the \
+             first invalid one.\n\
+             func New{id}({params}) ({id}, error) {{\n",
+            id = class.id,
+            error = error_enum.id,
+            params = fields
+                .iter()
+                .map(|(name, ty)| format!("{} {}", go_param(name), go_type(ty)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for constraint in &constraints {
+            let (_, ty) = fields
+                .iter()
+                .find(|(name, _)| name.as_str() == constraint.property)
+                .unwrap();
+            let failure = constraint_failure(
+                &regex_name(class, constraint),
+                constraint,
+                &go_param(constraint.property),
+                ty,
+            );
+            if failure.contains("utf8.") {
+                imports.insert("unicode/utf8".to_string());
+            }
+            if failure.contains("fmt.") {
+                imports.insert("fmt".to_string());
+            }
+            code.push_str(&format!(
+                "\tif {} {{\n\t\treturn {}{{}}, {}{}\n\t}}\n",
+                failure,
+                class.id,
+                error_enum.id,
+                go_name(constraint.variant)
+            ));
+        }
+        code.push_str(&format!(
+            "\treturn {}{{{}}}, nil\n}}\n",
+            class.id,
+            fields
+                .iter()
+                .map(|(name, _)| format!("{}: {}", go_name(name), go_param(name)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        Ok((code, imports))
+    }
+
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("// {}\n", desc));
+        }
+        code.push_str(&format!("type {} interface {{\n", interface.id));
+        for method in &interface.methods {
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("\t// {}\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            // Failures are plain errors, enums of `Result` implement `error` for it
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => String::new(),
+                Some(ReturnType::Type(ty)) => {
+                    primitive_imports(&ty, &IMPORTS, &mut imports);
+                    format!(" {}", go_type(&ty))
+                }
+                Some(ReturnType::Result { ok: Some(ok), .. }) => {
+                    primitive_imports(&ok, &IMPORTS, &mut imports);
+                    format!(" ({}, error)", go_type(&ok))
+                }
+                Some(ReturnType::Result { ok: None, .. }) => " error".to_string(),
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            // Async methods take a context to cancel them with
+            let mut params = method_params(&params, &mut imports);
+            if method.is_async {
+                imports.insert("context".to_string());
+                params.insert(0, "ctx context.Context".to_string());
+            }
+            code.push_str(&format!(
+                "\t{}({}){}\n",
+                go_name(&method.id),
+                params.join(", "),
+                returns
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, imports))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        for enum_def in &udl.enums {
+            let (gen_code, imports_) = self.gen_enum(udl, enum_def);
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        for class in &udl.models {
+            let error_enum = udl
+                .enums
+                .iter()
+                .find(|e| e.id == class.clone().error.unwrap_or_default());
+            let (gen_code, imports_) = self.gen_class(udl, class, error_enum)?;
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        for interface in &udl.interfaces {
+            let (gen_code, imports_) = self.gen_interface(interface)?;
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        // The last segment of the namespace, e.g. `app` for `com.billnchill.app`
+        let package = udl
+            .project
+            .namespace
+            .rsplit('.')
+            .next()
+            .map(|name| {
+                name.chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect::<String>()
+                    .to_ascii_lowercase()
+            })
+            .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .unwrap_or_else(|| "models".to_string());
+        let imports = if imports.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "import (\n{})\n\n",
+                imports
+                    .iter()
+                    .map(|import| format!("\t\"{}\"\n", import))
+                    .collect::<String>()
+            )
+        };
+        Ok(format!("package {}\n\n{}{}", package, imports, code))
+    }
+
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "gofmt".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        let status = std::process::Command::new("gofmt")
+            .arg("-w")
+            .arg(path)
+            .stdout(Stdio::piped())
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_go_type() {
+        for (udl_type, expected) in [
+            ("int64", "int64"),
+            ("uint8?", "*uint8"),
+            ("datetime^", "time.Time"),
+            ("decimal", "string"),
+            ("List<$enum::SortOrder>", "[]SortOrder"),
+            ("Map<string, Set<int?>>?", "map[string][]*int"),
+            ("bytes?", "[]byte"),
+        ] {
+            assert_eq!(go_type(&parse_type(udl_type).unwrap()), expected);
+        }
+        assert_eq!(go_regex(r"^\d+$"), "`^\\d+$`");
+        assert_eq!(go_regex("^`$"), "\"^`$\"");
+    }
+
+    #[test]
+    fn test_gen_enum() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let (code, imports) = GoGenerator().gen_enum(&udl, find("PaymentStatus"));
+        assert!(code.starts_with(
+            "// Status of an invoice\ntype PaymentStatus string\n\nconst (\n\
+             \t// Invoice not yet sent\n\tPaymentStatusKDraft PaymentStatus = \"draft\"\n"
+        ));
+        // Any string converts to a named string type, JSON rejects unknown values
+        assert!(code.contains("func (e *PaymentStatus) UnmarshalJSON(data []byte) error {\n"));
+        assert!(code.contains("\tif !PaymentStatus(value).IsValid() {\n"));
+        assert!(!code.contains("Error() string"));
+        assert_eq!(
+            imports,
+            BTreeSet::from(["encoding/json".to_string(), "fmt".to_string()])
+        );
+        // Errors of models and service methods implement `error`
+        for id in ["LoginError", "InvoiceStatus"] {
+            let (code, _) = GoGenerator().gen_enum(&udl, find(id));
+            assert!(code.contains(&format!(
+                "func (e {}) Error() string {{\n\treturn string(e)\n}}\n",
+                id
+            )));
+        }
+
+        // Variants share one struct, so the fields of the other variants must be left
+        // out: structs through a pointer, since `omitempty` never omits them
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Shipment
+            variants:
+              - id: K_PENDING
+                value: "pending"
+              - id: K_SHIPPED
+                value:
+                  shippedAt: datetime
+                  carrier: Carrier
+                  weight: uint32
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = GoGenerator().gen_enum(&udl, &enumm);
+        assert!(code.starts_with(
+            "type Shipment struct {\n\
+             \tType ShipmentType `json:\"type\"`\n\
+             \tShippedAt *time.Time `json:\"shipped_at,omitempty\"`\n\
+             \tCarrier *Carrier `json:\"carrier,omitempty\"`\n\
+             \tWeight uint32 `json:\"weight,omitempty\"`\n\
+             }\n\n\
+             // ShipmentType tags the variants of Shipment.\n\
+             type ShipmentType string\n\n\
+             const (\n\
+             \tShipmentTypeKPending ShipmentType = \"pending\"\n"
+        ));
+        assert!(imports.contains("time"));
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let generator = GoGenerator();
+
+        let (code, imports) = generator
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert_eq!(
+            code,
+            "// User login request\n\
+             type LoginRequest struct {\n\
+             \t// User email address\n\
+             \tEmail string `json:\"email\"`\n\
+             \tPassword string `json:\"password\"`\n\
+             \tRememberMe bool `json:\"remember_me\"`\n\
+             }\n\n\
+             var loginRequestEmailFormat = regexp.MustCompile(`^[^@\\s]+@[^@\\s]+\\.[^@\\s]+$`)\n\n\
+             // NewLoginRequest returns a LoginRequest if every field is valid, or else the \
+             LoginError of the first invalid one.\n\
+             func NewLoginRequest(email string, password string, rememberMe bool) \
+             (LoginRequest, error) {\n\
+             \tif !loginRequestEmailFormat.MatchString(email) {\n\
+             \t\treturn LoginRequest{}, LoginErrorKInvalidEmail\n\
+             \t}\n\
+             \tif utf8.RuneCountInString(password) < 8 {\n\
+             \t\treturn LoginRequest{}, LoginErrorKInvalidPasswordMin\n\
+             \t}\n\
+             \tif utf8.RuneCountInString(password) > 32 {\n\
+             \t\treturn LoginRequest{}, LoginErrorKInvalidPasswordMax\n\
+             \t}\n\
+             \treturn LoginRequest{Email: email, Password: password, RememberMe: rememberMe}, nil\n\
+             }\n"
+        );
+        assert_eq!(
+            imports,
+            BTreeSet::from(["regexp".to_string(), "unicode/utf8".to_string()])
+        );
+
+        // A nil pointer passes, `len` would count bytes instead of runes
+        let (code, _) = generator
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains("\tTag string `json:\"tag,omitempty\"`\n"));
+        assert!(code.contains("\tif notes != nil && utf8.RuneCountInString(*notes) > 500 {\n"));
+        assert!(code.contains("\tif taxRate > 100 {\n"));
+
+        let (code, imports) = generator.gen_class(&udl, find("Invoice"), None).unwrap();
+        assert!(code.contains("\tPaidAt *time.Time `json:\"paid_at,omitempty\"`\n"));
+        assert!(code.contains("\tCreatedAt time.Time `json:\"created_at\"`\n"));
+        assert!(!code.contains("func New"));
+        assert!(imports.contains("time"));
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+
+        // Invoices carry a `client_id`, spelled with its initialism
+        let (code, _) = generator
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains("\tClientID string `json:\"client_id\"`\n"));
+        assert!(code.contains("func NewCreateInvoiceRequest(clientID string, "));
+        assert!(code.contains("{ClientID: clientID, "));
+
+        // Models returned as errors by service methods implement `error`
+        let (code, imports) = generator.gen_class(&udl, find("ApiError"), None).unwrap();
+        assert!(code.contains(
+            "func (e ApiError) Error() string {\n\
+             \ttype plain ApiError\n\
+             \treturn fmt.Sprintf(\"ApiError%+v\", plain(e))\n\
+             }\n"
+        ));
+        assert!(imports.contains("fmt"));
+        let (code, _) = generator.gen_class(&udl, find("Invoice"), None).unwrap();
+        assert!(!code.contains("Error() string"));
+
+        let code = generator.generate(&udl).unwrap();
+        assert!(code.starts_with("package app\n\nimport (\n\t\"context\"\n"));
+    }
+
+    #[test]
+    fn test_optional_structs() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Delivery
+            properties:
+              updated_at: datetime^
+              invoice: Invoice^
+              status: $enum::InvoiceStatus^
+              notes: List<string>^
+            "#,
+        )
+        .unwrap();
+        let (code, _) = GoGenerator().gen_class(&udl, &class, None).unwrap();
+        // Zero strings and nil slices are omitted without a pointer
+        assert_eq!(
+            code,
+            "type Delivery struct {\n\
+             \tUpdatedAt *time.Time `json:\"updated_at,omitempty\"`\n\
+             \tInvoice *Invoice `json:\"invoice,omitempty\"`\n\
+             \tStatus InvoiceStatus `json:\"status,omitempty\"`\n\
+             \tNotes []string `json:\"notes,omitempty\"`\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = GoGenerator().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "// Invoice management operations\n\
+             type InvoiceService interface {\n\
+             \tFetchInvoices(ctx context.Context, fetchInvoicesRequest FetchInvoicesRequest) (Invoice, error)\n\
+             \t// Get a specific invoice by ID\n\
+             \tGetInvoice(id string, includeItems *bool) *Invoice\n\
+             \tDeleteInvoice(ctx context.Context, value string) error\n\
+             \tPing()\n\
+             }\n"
+        );
+        assert_eq!(imports, BTreeSet::from(["context".to_string()]));
+    }
+}
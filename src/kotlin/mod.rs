@@ -0,0 +1,917 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use convert_case::ccase;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    core::TypeDefault,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::{parse_type, primitive_imports},
+};
+
+pub(crate) struct KotlinGenerator();
+
+/// Types without a built-in `kotlinx.serialization` serializer keep the wire format
+/// of the Dart and Rust targets: decimals and base64 bytes are strings, durations
+/// are milliseconds.
+static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("bool", "Boolean"),
+        ("int", "Int"),
+        ("float", "Double"),
+        ("string", "String"),
+        ("isize", "Long"),
+        ("int8", "Byte"),
+        ("int16", "Short"),
+        ("int32", "Int"),
+        ("int64", "Long"),
+        ("usize", "ULong"),
+        ("uint8", "UByte"),
+        ("uint16", "UShort"),
+        ("uint32", "UInt"),
+        ("uint64", "ULong"),
+        ("float32", "Float"),
+        ("float64", "Double"),
+        ("datetime", "Instant"),
+        ("date", "LocalDate"),
+        ("object", "JsonElement"),
+        ("uuid", "String"),
+        ("decimal", "String"),
+        ("bytes", "String"),
+        ("url", "String"),
+        ("duration", "Long"),
+        ("email", "String"),
+    ])
+});
+
+/// Imports needed by primitives that are not part of the Kotlin standard library.
+static IMPORTS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("datetime", "import kotlinx.datetime.Instant\n"),
+        ("date", "import kotlinx.datetime.LocalDate\n"),
+        ("object", "import kotlinx.serialization.json.JsonElement\n"),
+    ])
+});
+
+/// Declared once per file when a validated model or a service method uses it, like
+/// `ResultDart` in Dart. Kotlin's own `Result` only carries exceptions.
+const RESULT_TYPE: &str = "sealed class Result<out T, out E> {
+    data class Success<out T>(val value: T) : Result<T, Nothing>()
+
+    data class Failure<out E>(val error: E) : Result<Nothing, E>()
+}\n\n";
+
+/// Kotlin type without the nullable marker.
+fn kotlin_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "Unit".to_string(),
+        // `List`, `Set` and `Map` are Kotlin's own collection names
+        _ => format!(
+            "{}<{}>",
+            ty.name(),
+            ty.args()
+                .into_iter()
+                .map(kotlin_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn kotlin_type(ty: &TypeExpr) -> String {
+    format!(
+        "{}{}",
+        kotlin_base_type(ty),
+        if ty.nullable { "?" } else { "" }
+    )
+}
+
+/// Escaped Kotlin string literal of `value`, `$` included so it is never a template.
+fn kotlin_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Kotlin literal of a number or bool `value` for the Kotlin type `base`.
+fn kotlin_literal(value: &str, base: &str) -> String {
+    match base {
+        "String" => kotlin_string(value),
+        "Long" => format!("{}L", value),
+        "Float" => format!("{}f", value),
+        "Double" if !value.contains(['.', 'e', 'E']) => format!("{}.0", value),
+        "UByte" | "UShort" | "UInt" => format!("{}u", value),
+        "ULong" => format!("{}uL", value),
+        _ => value.to_string(),
+    }
+}
+
+/// Kotlin literal of a resolved default.
+fn kotlin_default(default: &TypeDefault, ty: &TypeExpr) -> Option<String> {
+    match default {
+        TypeDefault::Variant(enumm, variant) => Some(format!("{}.{}", enumm.id, variant.id())),
+        TypeDefault::Value(value) if value == "null" && ty.nullable => Some("null".to_string()),
+        TypeDefault::Value(value) => Some(kotlin_literal(value, &kotlin_base_type(ty))),
+    }
+}
+
+/// Zero value of a type, used for optional fields without a default.
+fn kotlin_zero_value(ty: &TypeExpr) -> Option<String> {
+    let zero = match &ty.kind {
+        TypeKind::Primitive(_) if ty.is_number() => kotlin_literal("0", &kotlin_base_type(ty)),
+        TypeKind::Primitive(name) => match kotlin_base_type(ty).as_str() {
+            "Boolean" => "false".to_string(),
+            "String" if name != "decimal" => "\"\"".to_string(),
+            _ => return None,
+        },
+        TypeKind::List(_) => "emptyList()".to_string(),
+        TypeKind::Set(_) => "emptySet()".to_string(),
+        TypeKind::Map(..) => "emptyMap()".to_string(),
+        _ => return None,
+    };
+    Some(zero)
+}
+
+/// Hard keywords, which need backticks to be used as names.
+const KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// camelCase Kotlin name of a property, param or method, in backticks when it is a
+/// keyword.
+fn kotlin_name(name: &str) -> String {
+    let name = ccase!(camel, name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("`{}`", name)
+    } else {
+        name
+    }
+}
+
+/// `@SerialName` of a property or payload field whose wire name differs from its
+/// Kotlin name.
+fn serial_name(name: &str) -> String {
+    if ccase!(camel, name) == ccase!(snake, name) {
+        String::new()
+    } else {
+        format!("@SerialName(\"{}\") ", ccase!(snake, name))
+    }
+}
+
+/// Parameter list of a service method, adding the imports of the param types.
+fn method_params(params: &[(Option<&str>, TypeExpr)], imports: &mut BTreeSet<String>) -> String {
+    params
+        .iter()
+        .map(|(name, ty)| {
+            primitive_imports(ty, &IMPORTS, imports);
+            let name = kotlin_name(param_name(*name, ty));
+            format!("{}: {}", name, kotlin_type(ty))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Name of the `Regex` a format or pattern constraint is checked with.
+fn regex_name(constraint: &Constraint) -> String {
+    let suffix = match constraint.check {
+        Check::Pattern(_) => "PATTERN",
+        _ => "FORMAT",
+    };
+    format!("{}_{}", ccase!(constant, constraint.property), suffix)
+}
+
+/// Condition under which `constraint` fails for the parameter `name`. Limits compare
+/// numbers by value, strings by `codePointCount` rather than their UTF-16 `length`,
+/// and collections by `size`. Patterns match the value as a string. `null` passes.
+fn constraint_failure(constraint: &Constraint, name: &str, ty: &TypeExpr) -> String {
+    let base = kotlin_base_type(ty);
+    match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            let limit = if ty.is_number() {
+                kotlin_literal(&limit.to_string(), &base)
+            } else {
+                limit.to_string()
+            };
+            let length = |name: &str| {
+                if base == "String" {
+                    format!("{}.codePointCount(0, {}.length)", name, name)
+                } else {
+                    format!("{}.size", name)
+                }
+            };
+            let value = match (ty.is_number(), ty.nullable) {
+                (true, false) => name.to_string(),
+                (true, true) => format!("({} ?: {})", name, limit),
+                (false, true) => format!("({}?.let {{ {} }} ?: {})", name, length("it"), limit),
+                (false, false) => length(name),
+            };
+            format!("{} {} {}", value, operator, limit)
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            let value = if base == "String" {
+                name.to_string()
+            } else {
+                format!("{}.toString()", name)
+            };
+            let matches = format!("{}.containsMatchIn({})", regex_name(constraint), value);
+            if ty.nullable {
+                format!("{} != null && !{}", name, matches)
+            } else {
+                format!("!{}", matches)
+            }
+        }
+    }
+}
+
+impl LangGenerator for KotlinGenerator {
+    fn extension(&self) -> &str {
+        "kt"
+    }
+
+    fn gen_enum(&self, _udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut imports = BTreeSet::new();
+        imports.insert("import kotlinx.serialization.Serializable\n".to_string());
+        let mut code = String::new();
+        if let Some(desc) = enumm.description.as_ref() {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        if enumm.is_tagged() {
+            // Polymorphic serialization tags variants with `type`, like the Rust
+            // enum, e.g. `{ "type": "K_PAID", "amount": 10 }`
+            code.push_str(&format!("@Serializable\nsealed class {} {{\n", enumm.id));
+            for (i, variant) in enumm.variants.iter().enumerate() {
+                if i > 0 {
+                    code.push('\n');
+                }
+                if let EnumKind::Complex(EnumVariant {
+                    description: Some(desc),
+                    ..
+                }) = variant
+                {
+                    code.push_str(&format!("    /** {} */\n", desc));
+                }
+                code.push_str(&format!(
+                    "    @Serializable\n    @SerialName({})\n",
+                    kotlin_string(variant.wire_value())
+                ));
+                match variant.fields() {
+                    Some(map) => {
+                        let fields = map
+                            .iter()
+                            .map(|(key, value)| {
+                                let ty = match parse_type(value) {
+                                    Some(ty) => {
+                                        primitive_imports(&ty, &IMPORTS, &mut imports);
+                                        kotlin_type(&ty)
+                                    }
+                                    None => value.clone(),
+                                };
+                                format!("{}val {}: {}", serial_name(key), kotlin_name(key), ty)
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        code.push_str(&format!(
+                            "    data class {}({}) : {}()\n",
+                            variant.id(),
+                            fields,
+                            enumm.id
+                        ));
+                    }
+                    None => {
+                        code.push_str(&format!(
+                            "    data object {} : {}()\n",
+                            variant.id(),
+                            enumm.id
+                        ));
+                    }
+                }
+            }
+            code.push_str("}\n");
+        } else if enumm
+            .variants
+            .iter()
+            .all(|variant| matches!(variant, EnumKind::Simple(_)))
+        {
+            code.push_str(&format!(
+                "@Serializable\nenum class {} {{\n    {},\n}}\n",
+                enumm.id,
+                enumm
+                    .variants
+                    .iter()
+                    .map(|variant| variant.id())
+                    .collect::<Vec<_>>()
+                    .join(",\n    ")
+            ));
+        } else {
+            // Like the Dart enum, every constant carries its JSON value
+            code.push_str(&format!(
+                "@Serializable\nenum class {}(val value: String) {{\n",
+                enumm.id
+            ));
+            for variant in &enumm.variants {
+                if let EnumKind::Complex(EnumVariant {
+                    description: Some(desc),
+                    ..
+                }) = variant
+                {
+                    code.push_str(&format!("    /** {} */\n", desc));
+                }
+                let value = variant.wire_value();
+                if value != variant.id() {
+                    code.push_str(&format!("    @SerialName({})\n", kotlin_string(value)));
+                }
+                code.push_str(&format!(
+                    "    {}({}),\n",
+                    variant.id(),
+                    kotlin_string(value)
+                ));
+            }
+            code.push_str("}\n");
+        }
+        if code.contains("@SerialName(") {
+            imports.insert("import kotlinx.serialization.SerialName\n".to_string());
+        }
+        (code, imports)
+    }
+
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        imports.insert("import kotlinx.serialization.Serializable\n".to_string());
+        let mut code = String::new();
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        let validated = is_validated(class);
+        let mut fields = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            primitive_imports(&ty, &IMPORTS, &mut imports);
+            let default = udl
+                .property_default(prop, &ty)
+                .and_then(|default| kotlin_default(&default, &ty));
+            // Optional fields fall back to the zero value of their type, or to null
+            // when it has none
+            let (ty, default) = match default {
+                None if ty.optional && !ty.nullable => match kotlin_zero_value(&ty) {
+                    Some(zero) => (ty, Some(zero)),
+                    None => (
+                        TypeExpr {
+                            nullable: true,
+                            ..ty
+                        },
+                        Some("null".to_string()),
+                    ),
+                },
+                default => (ty, default),
+            };
+            fields.push((name, prop.get(&PropertyKey::Description), ty, default));
+        }
+
+        // `Json` skips values equal to their default unless `encodeDefaults` is set,
+        // the other targets always write them
+        let encode_defaults = fields.iter().any(|(.., default)| default.is_some());
+        if encode_defaults {
+            imports.insert("import kotlinx.serialization.EncodeDefault\n".to_string());
+            imports
+                .insert("import kotlinx.serialization.ExperimentalSerializationApi\n".to_string());
+        }
+        // Validated models are only built through `build()`, `copy()` included
+        code.push_str(&format!(
+            "@Serializable\n{}{}data class {}{}(\n",
+            if encode_defaults {
+                "@OptIn(ExperimentalSerializationApi::class)\n"
+            } else {
+                ""
+            },
+            if validated {
+                "@ConsistentCopyVisibility\n"
+            } else {
+                ""
+            },
+            class.id,
+            if validated {
+                " private constructor"
+            } else {
+                ""
+            }
+        ));
+        for (name, desc, ty, default) in &fields {
+            if let Some(desc) = desc {
+                code.push_str(&format!("    /** {} */\n", desc));
+            }
+            code.push_str(&format!(
+                "    {}{}val {}: {}{},\n",
+                if default.is_some() {
+                    "@EncodeDefault "
+                } else {
+                    ""
+                },
+                serial_name(name),
+                kotlin_name(name),
+                kotlin_type(ty),
+                default
+                    .as_ref()
+                    .map_or(String::new(), |default| format!(" = {}", default))
+            ));
+        }
+        if fields
+            .iter()
+            .any(|(name, ..)| !serial_name(name).is_empty())
+        {
+            imports.insert("import kotlinx.serialization.SerialName\n".to_string());
+        }
+
+        if !validated {
+            code.push_str(")\n");
+            return Ok((code, imports));
+        }
+        let Some(error_enum) = error_enum else {
+            return Err(UdlError::MissingErrorEnum {
+                model: class.id.clone(),
+                error: class.error.clone().unwrap_or_default(),
+            });
+        };
+        let args = fields
+            .iter()
+            .map(|(name, ..)| kotlin_name(name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // Decoding skips `build()` but still runs `init`
+        code.push_str(&format!(
+            ") {{\n    init {{\n        \
+             firstError({})?.let {{ throw IllegalArgumentException(it.toString()) }}\n    \
+             }}\n\n    companion object {{\n",
+            args
+        ));
+        let constraints = constraints(class, error_enum)?;
+        for constraint in &constraints {
+            if let Some(pattern) = constraint.check.pattern() {
+                code.push_str(&format!(
+                    "        private val {} = Regex({})\n",
+                    regex_name(constraint),
+                    kotlin_string(pattern)
+                ));
+            }
+        }
+        if constraints.iter().any(|c| c.check.pattern().is_some()) {
+            code.push('\n');
+        }
+        code.push_str(&format!(
+            "        fun build({}): Result<{}, {}> {{\n            \
+             firstError({})?.let {{ return Result.Failure(it) }}\n",
+            fields
+                .iter()
+                .map(|(name, _, ty, default)| format!(
+                    "{}: {}{}",
+                    kotlin_name(name),
+                    kotlin_type(ty),
+                    default
+                        .as_ref()
+                        .map_or(String::new(), |default| format!(" = {}", default))
+                ))
+                .collect::<Vec<_>>()
+                .join(", "),
+            class.id,
+            error_enum.id,
+            args
+        ));
+        code.push_str(&format!(
+            "            return Result.Success({}({}))\n        }}\n\n",
+            class.id,
+            fields
+                .iter()
+                .map(|(name, ..)| format!("{} = {}", kotlin_name(name), kotlin_name(name)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        code.push_str(&format!(
+            "        private fun firstError({}): {}? {{\n",
+            fields
+                .iter()
+                .map(|(name, _, ty, _)| format!("{}: {}", kotlin_name(name), kotlin_type(ty)))
+                .collect::<Vec<_>>()
+                .join(", "),
+            error_enum.id
+        ));
+        for constraint in &constraints {
+            let (_, _, ty, _) = fields
+                .iter()
+                .find(|(name, ..)| name.as_str() == constraint.property)
+                .unwrap();
+            code.push_str(&format!(
+                "            if ({}) return {}.{}\n",
+                constraint_failure(constraint, &kotlin_name(constraint.property), ty),
+                error_enum.id,
+                constraint.variant
+            ));
+        }
+        code.push_str("            return null\n        }\n    }\n}\n");
+        Ok((code, imports))
+    }
+
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        code.push_str(&format!("interface {} {{\n", interface.id));
+        for method in &interface.methods {
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("    /** {} */\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => String::new(),
+                Some(ReturnType::Type(ty)) => {
+                    primitive_imports(&ty, &IMPORTS, &mut imports);
+                    format!(": {}", kotlin_type(&ty))
+                }
+                Some(ReturnType::Result { ok, err }) => {
+                    for ty in ok.iter().chain([&err]) {
+                        primitive_imports(ty, &IMPORTS, &mut imports);
+                    }
+                    let ok = ok.as_ref().map_or("Unit".to_string(), kotlin_type);
+                    format!(": Result<{}, {}>", ok, kotlin_type(&err))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            code.push_str(&format!(
+                "    {}fun {}({}){}\n",
+                if method.is_async { "suspend " } else { "" },
+                kotlin_name(method.id.as_str()),
+                method_params(&params, &mut imports),
+                returns
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, imports))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        for enum_def in &udl.enums {
+            let (gen_code, imports_) = self.gen_enum(udl, enum_def);
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        for class in &udl.models {
+            let error_enum = udl
+                .enums
+                .iter()
+                .find(|e| e.id == class.clone().error.unwrap_or_default());
+            let (gen_code, imports_) = self.gen_class(udl, class, error_enum)?;
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        for interface in &udl.interfaces {
+            let (gen_code, imports_) = self.gen_interface(interface)?;
+            code.push_str(&gen_code);
+            code.push('\n');
+            imports.extend(imports_);
+        }
+
+        if code.contains("Result<") {
+            code.insert_str(0, RESULT_TYPE);
+        }
+        let package = if udl.project.namespace.is_empty() {
+            String::new()
+        } else {
+            format!("package {}\n\n", udl.project.namespace)
+        };
+        Ok(format!(
+            "{}{}\n{}",
+            package,
+            imports.into_iter().collect::<String>(),
+            code
+        ))
+    }
+
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "ktfmt".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        let status = std::process::Command::new("ktfmt")
+            .arg("--kotlinlang-style")
+            .arg(path)
+            .stdout(Stdio::piped())
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kotlin_type() {
+        for (udl_type, expected) in [
+            ("int64", "Long"),
+            ("uint8?", "UByte?"),
+            ("datetime^", "Instant"),
+            ("decimal", "String"),
+            ("List<$enum::SortOrder>", "List<SortOrder>"),
+            ("Map<string, Set<int?>>?", "Map<String, Set<Int?>>?"),
+        ] {
+            assert_eq!(kotlin_type(&parse_type(udl_type).unwrap()), expected);
+        }
+        assert_eq!(kotlin_literal("20", "Long"), "20L");
+        assert_eq!(kotlin_literal("2", "Double"), "2.0");
+        assert_eq!(kotlin_literal("2", "UInt"), "2u");
+        assert_eq!(kotlin_literal("costs $5", "String"), "\"costs \\$5\"");
+    }
+
+    #[test]
+    fn test_gen_enum() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let (code, _) = KotlinGenerator().gen_enum(&udl, find("SortOrder"));
+        assert_eq!(
+            code,
+            "@Serializable\nenum class SortOrder {\n    K_ASC,\n    K_DESC,\n}\n"
+        );
+        let (code, imports) = KotlinGenerator().gen_enum(&udl, find("PaymentStatus"));
+        assert!(code.starts_with(
+            "/** Status of an invoice */\n\
+             @Serializable\n\
+             enum class PaymentStatus(val value: String) {\n\
+             \x20   /** Invoice not yet sent */\n\
+             \x20   @SerialName(\"draft\")\n\
+             \x20   K_DRAFT(\"draft\"),\n"
+        ));
+        assert!(imports.contains("import kotlinx.serialization.SerialName\n"));
+
+        // Polymorphic variants are tagged with their `@SerialName`, `$` must not start
+        // a string template
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Event
+            variants:
+              - id: K_PING
+                value: "ping$"
+              - id: K_SCHEDULED
+                value:
+                  when: datetime
+                  in: string?
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = KotlinGenerator().gen_enum(&udl, &enumm);
+        assert_eq!(
+            code,
+            "@Serializable\n\
+             sealed class Event {\n\
+             \x20   @Serializable\n\
+             \x20   @SerialName(\"ping\\$\")\n\
+             \x20   data object K_PING : Event()\n\n\
+             \x20   @Serializable\n\
+             \x20   @SerialName(\"K_SCHEDULED\")\n\
+             \x20   data class K_SCHEDULED(val `when`: Instant, val `in`: String?) : Event()\n\
+             }\n"
+        );
+        assert!(imports.contains("import kotlinx.datetime.Instant\n"));
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let generator = KotlinGenerator();
+
+        let (code, _) = generator
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert!(code.starts_with(
+            "/** User login request */\n\
+             @Serializable\n\
+             @OptIn(ExperimentalSerializationApi::class)\n\
+             @ConsistentCopyVisibility\n\
+             data class LoginRequest private constructor(\n"
+        ));
+        // `Json` would drop a `false` equal to the default without `@EncodeDefault`
+        assert!(code.contains(
+            "    @EncodeDefault @SerialName(\"remember_me\") val rememberMe: Boolean = false,\n"
+        ));
+        assert!(code.contains(
+            "private val EMAIL_FORMAT = Regex(\"^[^@\\\\s]+@[^@\\\\s]+\\\\.[^@\\\\s]+\\$\")"
+        ));
+        assert!(code.contains(
+            "fun build(email: String, password: String, rememberMe: Boolean = false): \
+             Result<LoginRequest, LoginError> {"
+        ));
+        assert!(code.contains(
+            "return Result.Success(LoginRequest(email = email, password = password, \
+             rememberMe = rememberMe))"
+        ));
+        // Decoding runs the same checks through `init`
+        assert!(code.contains(
+            "    init {\n        firstError(email, password, rememberMe)?.let { \
+             throw IllegalArgumentException(it.toString()) }\n    }\n"
+        ));
+        assert!(code.contains(
+            "private fun firstError(email: String, password: String, rememberMe: Boolean): \
+             LoginError? {"
+        ));
+        assert!(code.contains(
+            "if (!EMAIL_FORMAT.containsMatchIn(email)) return LoginError.K_INVALID_EMAIL\n"
+        ));
+
+        // `length` counts UTF-16 units, `codePointCount` counts code points
+        let (code, _) = generator
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(
+            code.contains(
+                "if ((notes?.let { it.codePointCount(0, it.length) } ?: 500) > 500) return"
+            )
+        );
+        assert!(code.contains("if (taxRate > 100) return"));
+
+        // Models without validators keep a public constructor and defaults
+        let (code, _) = generator
+            .gen_class(&udl, find("FetchInvoicesRequest"), None)
+            .unwrap();
+        assert!(code.contains(
+            "@Serializable\n@OptIn(ExperimentalSerializationApi::class)\ndata class FetchInvoicesRequest(\n"
+        ));
+        assert!(code.contains("    @EncodeDefault val limit: Byte = 20,\n"));
+        assert!(code.contains(
+            "    @EncodeDefault @SerialName(\"sort_order\") val sortOrder: SortOrder = SortOrder.K_DESC,\n"
+        ));
+        assert!(code.contains("    val status: InvoiceStatus?,\n"));
+        assert!(!code.contains("companion object"));
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+
+        let code = generator.generate(&udl).unwrap();
+        assert!(code.starts_with("package com.billnchill.app\n\nimport "));
+        assert_eq!(code.matches("sealed class Result<").count(), 1);
+    }
+
+    #[test]
+    fn test_keywords() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let error: Enum = serde_yaml::from_str(
+            r#"
+            id: ScheduleError
+            variants:
+              - id: K_INVALID_CLASS
+                value: "Class name is too long"
+                target: "limit:max"
+                target_field: class
+            "#,
+        )
+        .unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Schedule
+            error: ScheduleError
+            properties:
+              when: datetime
+              class:
+                type: string
+                limit: ...10
+            "#,
+        )
+        .unwrap();
+        let (code, _) = KotlinGenerator()
+            .gen_class(&udl, &class, Some(&error))
+            .unwrap();
+        assert!(code.contains("    val `when`: Instant,\n    val `class`: String,\n"));
+        assert!(code.contains("fun build(`when`: Instant, `class`: String)"));
+        assert!(code.contains("if (`class`.codePointCount(0, `class`.length) > 10)"));
+        assert!(code.contains("Schedule(`when` = `when`, `class` = `class`)"));
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, _) = KotlinGenerator().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "/** Invoice management operations */\n\
+             interface InvoiceService {\n\
+             \x20   suspend fun fetchInvoices(fetchInvoicesRequest: FetchInvoicesRequest): Result<Invoice, InvoiceStatus>\n\
+             \x20   /** Get a specific invoice by ID */\n\
+             \x20   fun getInvoice(id: String, includeItems: Boolean?): Invoice?\n\
+             \x20   suspend fun deleteInvoice(value: String): Result<Unit, ApiError>\n\
+             \x20   fun ping()\n\
+             }\n"
+        );
+    }
+}
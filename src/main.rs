@@ -1,40 +1,190 @@
+mod cli;
 mod dart;
+mod docs;
+mod go;
+mod kotlin;
+mod python;
 mod rust;
+mod swift;
+mod typescript;
 mod udl;
 
-use std::io::Write;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
+use clap::Parser;
 use udl::*;
 
-use crate::{dart::DartGenerator, rust::RustGenerator};
-
-const EXAMPLES_DIR: &str = "examples";
-const OUTPUT_DIR: &str = "gen";
-const OUTPUT_FILE: &str = "output";
-
-fn main() {
-    let file = std::fs::File::open(format!("{EXAMPLES_DIR}/billing_app.yaml")).unwrap();
-    let str = std::io::read_to_string(file).unwrap();
-    let udl: UDL = serde_yaml::from_str(&str).unwrap();
-    // println!("{:#?}", udl);
-    let generators: Vec<Box<dyn LangGenerator>> =
-        vec![Box::new(DartGenerator()), Box::new(RustGenerator())];
-    for generator in generators {
-        let code = generator.generate(&udl);
-
-        let _ = std::fs::create_dir_all(OUTPUT_DIR);
-        let output_file = std::fs::File::create(format!(
-            "{OUTPUT_DIR}/{OUTPUT_FILE}.{}",
-            generator.extension()
-        ))
-        .unwrap();
-        let mut output = std::io::BufWriter::new(output_file);
-        output.write_all(code.as_bytes()).unwrap();
-        output.flush().unwrap();
-
-        generator.format(&format!(
-            "{OUTPUT_DIR}/{OUTPUT_FILE}.{}",
-            generator.extension()
+use crate::{
+    cli::{Cli, Command, FmtArgs, GenerateArgs, InputArgs, OutputArgs},
+    docs::DocsGenerator,
+    udl::{
+        manifest::{Manifest, SourceFile},
+        validator::validate,
+    },
+};
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> UdlError {
+    let path = path.to_path_buf();
+    move |source| UdlError::Io { path, source }
+}
+
+/// Parses every input and runs `f` on it, printing a located diagnostic for each
+/// manifest that fails instead of stopping at the first one.
+fn for_each_manifest(
+    inputs: &[PathBuf],
+    mut f: impl FnMut(&SourceFile, &Manifest) -> Result<(), UdlError>,
+) -> Result<(), UdlError> {
+    let mut failed = 0;
+    for input in inputs {
+        let file = match SourceFile::read(input) {
+            Ok(file) => file,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed += 1;
+                continue;
+            }
+        };
+        let (err, spans) = match file.parse() {
+            Err(err) => (err, None),
+            Ok(manifest) => match f(&file, &manifest) {
+                Ok(()) => continue,
+                Err(err) => (err, Some(manifest.spans)),
+            },
+        };
+        eprint!("{}", file.render(&err, spans.as_ref()));
+        failed += 1;
+    }
+    if failed > 0 {
+        return Err(UdlError::Failed { count: failed });
+    }
+    Ok(())
+}
+
+/// Reports every semantic problem in the manifest, failing if there were any.
+fn validated(file: &SourceFile, manifest: &Manifest) -> Result<(), UdlError> {
+    let errors = validate(&manifest.udl);
+    for err in &errors {
+        eprint!("{}", file.render(err, Some(&manifest.spans)));
+    }
+    if !errors.is_empty() {
+        return Err(UdlError::Invalid {
+            path: file.path.clone(),
+            count: errors.len(),
+        });
+    }
+    Ok(())
+}
+
+fn write_output(
+    generator: &dyn LangGenerator,
+    udl: &UDL,
+    out_path: &Path,
+    format: bool,
+) -> Result<PathBuf, UdlError> {
+    let code = generator.generate(udl)?;
+
+    if let Some(dir) = out_path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error(dir))?;
+    }
+    let path = out_path.with_extension(generator.extension());
+    let output_file = std::fs::File::create(&path).map_err(io_error(&path))?;
+    let mut output = std::io::BufWriter::new(output_file);
+    output
+        .write_all(code.as_bytes())
+        .and_then(|_| output.flush())
+        .map_err(io_error(&path))?;
+
+    if format {
+        generator.format(&path.to_string_lossy())?;
+    }
+    Ok(path)
+}
+
+fn check_out_name(args: &OutputArgs) -> Result<(), UdlError> {
+    if args.out_name.is_some() && args.input.inputs.len() > 1 {
+        return Err(UdlError::InvalidArgument(
+            "--out-name can only be used with a single input".to_string(),
         ));
     }
+    Ok(())
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), UdlError> {
+    check_out_name(&args.output)?;
+    for_each_manifest(&args.output.input.inputs, |file, manifest| {
+        validated(file, manifest)?;
+        let out_path = args.output.out_path(&file.path);
+        for target in &args.targets {
+            let generator = target.generator(args);
+            let path = write_output(
+                generator.as_ref(),
+                &manifest.udl,
+                &out_path,
+                !args.no_format,
+            )?;
+            println!("Generated {}", path.display());
+        }
+        Ok(())
+    })
+}
+
+fn check(args: &InputArgs) -> Result<(), UdlError> {
+    for_each_manifest(&args.inputs, |file, manifest| {
+        validated(file, manifest)?;
+        println!("{}: ok", file.path.display());
+        Ok(())
+    })
+}
+
+fn fmt(args: &FmtArgs) -> Result<(), UdlError> {
+    let mut unformatted = Vec::new();
+    for_each_manifest(&args.input.inputs, |file, manifest| {
+        let formatted = serde_yaml::to_string(&manifest.udl).map_err(UdlError::Serialize)?;
+        let formatted = formatted.trim_start_matches("---\n");
+        if args.check {
+            if file.text != formatted {
+                unformatted.push(file.path.display().to_string());
+            }
+        } else if args.write {
+            std::fs::write(&file.path, formatted).map_err(io_error(&file.path))?;
+        } else {
+            print!("{}", formatted);
+        }
+        Ok(())
+    })?;
+    if !unformatted.is_empty() {
+        return Err(UdlError::Unformatted { paths: unformatted });
+    }
+    Ok(())
+}
+
+fn docs(args: &OutputArgs) -> Result<(), UdlError> {
+    check_out_name(args)?;
+    for_each_manifest(&args.input.inputs, |file, manifest| {
+        let out_path = args.out_path(&file.path);
+        let path = write_output(&DocsGenerator(), &manifest.udl, &out_path, false)?;
+        println!("Generated {}", path.display());
+        Ok(())
+    })
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Generate(args) => generate(args),
+        Command::Check(args) => check(args),
+        Command::Fmt(args) => fmt(args),
+        Command::Docs(args) => docs(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,926 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use clap::{Args, ValueEnum};
+use convert_case::ccase;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    core::TypeDefault,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::{parse_type, primitive_imports},
+};
+
+#[derive(Default)]
+pub(crate) struct PythonGenerator(pub PythonOptions);
+
+/// Python specific options of `udl generate`.
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct PythonOptions {
+    /// Library the model classes are generated for
+    #[arg(long, value_enum, default_value_t)]
+    pub python_models: PythonModels,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum PythonModels {
+    /// Standard library `@dataclass`es, type hints only
+    #[default]
+    Dataclass,
+    /// pydantic `BaseModel`s, which also parse and serialize JSON
+    Pydantic,
+}
+
+/// Field names are the snake_case wire names, so only types matter here. Decimals
+/// parse from and serialize to strings in pydantic, base64 bytes stay strings and
+/// durations milliseconds, like the Dart and Rust targets.
+static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("bool", "bool"),
+        ("int", "int"),
+        ("float", "float"),
+        ("string", "str"),
+        ("isize", "int"),
+        ("int8", "int"),
+        ("int16", "int"),
+        ("int32", "int"),
+        ("int64", "int"),
+        ("usize", "int"),
+        ("uint8", "int"),
+        ("uint16", "int"),
+        ("uint32", "int"),
+        ("uint64", "int"),
+        ("float32", "float"),
+        ("float64", "float"),
+        ("datetime", "datetime"),
+        ("date", "date"),
+        ("object", "Any"),
+        ("uuid", "UUID"),
+        ("decimal", "Decimal"),
+        ("bytes", "str"),
+        ("url", "str"),
+        ("duration", "int"),
+        ("email", "str"),
+    ])
+});
+
+/// Imports needed by primitives that are not builtins.
+static IMPORTS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("datetime", "from datetime import datetime\n"),
+        ("date", "from datetime import date\n"),
+        ("object", "from typing import Any\n"),
+        ("uuid", "from uuid import UUID\n"),
+        ("decimal", "from decimal import Decimal\n"),
+    ])
+});
+
+/// Declared once per file when a service method uses it, like the `Result` of the
+/// other targets. Validated models raise instead.
+const RESULT_TYPE: &str = "T = TypeVar(\"T\")
+E = TypeVar(\"E\")
+
+
+@dataclass(frozen=True)
+class Ok(Generic[T]):
+    value: T
+
+
+@dataclass(frozen=True)
+class Err(Generic[E]):
+    error: E
+
+
+Result = Union[Ok[T], Err[E]]\n\n\n";
+
+/// Python type without the `Optional` wrapper.
+fn python_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "None".to_string(),
+        kind => format!(
+            "{}[{}]",
+            match kind {
+                TypeKind::List(_) => "list",
+                TypeKind::Set(_) => "set",
+                TypeKind::Map(..) => "dict",
+                _ => ty.name(),
+            },
+            ty.args()
+                .into_iter()
+                .map(python_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn python_type(ty: &TypeExpr) -> String {
+    if ty.nullable {
+        format!("Optional[{}]", python_base_type(ty))
+    } else {
+        python_base_type(ty)
+    }
+}
+
+/// Python type of a field of a set element or map key, where lists are tuples so
+/// that the frozen class hashes.
+fn python_hashable_type(ty: &TypeExpr) -> String {
+    let base = match &ty.kind {
+        TypeKind::List(item) => format!("tuple[{}, ...]", python_hashable_type(item)),
+        _ => python_base_type(ty),
+    };
+    if ty.nullable {
+        format!("Optional[{}]", base)
+    } else {
+        base
+    }
+}
+
+/// Keywords, which can't be used as names.
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// snake_case Python name of a field, param or method, with a trailing `_` when it
+/// is a keyword, e.g. `from_`.
+fn python_name(name: &str) -> String {
+    let name = ccase!(snake, name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// Adds the imports of every type nested in `ty`.
+fn type_imports(ty: &TypeExpr, imports: &mut BTreeSet<String>) {
+    primitive_imports(ty, &IMPORTS, imports);
+    ty.visit(&mut |ty| {
+        if ty.nullable {
+            imports.insert("from typing import Optional\n".to_string());
+        }
+    });
+}
+
+/// Escaped Python string literal of `value`.
+fn python_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// `r"..."` literal of a regex. Raw strings can't hold the quote, a newline or a
+/// trailing backslash, such patterns get an escaped literal.
+fn python_regex(pattern: &str) -> String {
+    if pattern.contains(['"', '\n']) || pattern.ends_with('\\') {
+        python_string(pattern)
+    } else {
+        format!("r\"{}\"", pattern)
+    }
+}
+
+/// Python literal of a `value` for the Python type `base`, if it has one.
+fn python_literal(value: &str, base: &str) -> Option<String> {
+    let literal = match base {
+        "str" => python_string(value),
+        "bool" if value == "true" => "True".to_string(),
+        "bool" => "False".to_string(),
+        "float" if !value.contains(['.', 'e', 'E']) => format!("{}.0", value),
+        "int" | "float" => value.to_string(),
+        _ => return None,
+    };
+    Some(literal)
+}
+
+/// Python literal of a resolved default. Defaults of tagged enums are unit variants,
+/// constructed without arguments.
+fn python_default(default: &TypeDefault, ty: &TypeExpr) -> Option<String> {
+    match default {
+        TypeDefault::Variant(enumm, variant) if enumm.is_tagged() => {
+            Some(format!("{}{}()", enumm.id, ccase!(pascal, variant.id())))
+        }
+        TypeDefault::Variant(enumm, variant) => Some(format!("{}.{}", enumm.id, variant.id())),
+        TypeDefault::Value(value) if value == "null" => ty.nullable.then(|| "None".to_string()),
+        TypeDefault::Value(value) => python_literal(value, &python_base_type(ty)),
+    }
+}
+
+/// Annotated params of a service method, each with a leading `, ` to follow `self`.
+fn method_params(params: &[(Option<&str>, TypeExpr)], imports: &mut BTreeSet<String>) -> String {
+    params
+        .iter()
+        .map(|(name, ty)| {
+            type_imports(ty, imports);
+            let name = python_name(param_name(*name, ty));
+            format!(", {}: {}", name, python_type(ty))
+        })
+        .collect()
+}
+
+/// Condition under which `constraint` fails for the field `name`. Limits compare
+/// numbers by value and anything else by `len()`, which counts code points for
+/// strings. Patterns match the value as a string. `None` passes.
+fn constraint_failure(constraint: &Constraint, name: &str, ty: &TypeExpr) -> String {
+    let value = format!("self.{}", name);
+    let check = match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            if ty.is_number() {
+                format!("{} {} {}", value, operator, limit)
+            } else {
+                format!("len({}) {} {}", value, operator, limit)
+            }
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            let pattern = constraint.check.pattern().unwrap_or_default();
+            if python_base_type(ty) == "str" {
+                format!("not re.search({}, {})", python_regex(pattern), value)
+            } else {
+                format!("not re.search({}, str({}))", python_regex(pattern), value)
+            }
+        }
+    };
+    if ty.nullable {
+        format!("{} is not None and {}", value, check)
+    } else {
+        check
+    }
+}
+
+impl PythonGenerator {
+    fn pydantic(&self) -> bool {
+        self.0.python_models == PythonModels::Pydantic
+    }
+
+    /// Header of a model class, frozen when `frozen`.
+    fn class_header(&self, id: &str, frozen: bool, imports: &mut BTreeSet<String>) -> String {
+        if self.pydantic() {
+            imports.insert("from pydantic import BaseModel\n".to_string());
+            format!("class {}(BaseModel):\n", id)
+        } else {
+            // Keyword-only, so fields with defaults may come before required ones
+            imports.insert("from dataclasses import dataclass\n".to_string());
+            format!(
+                "@dataclass({}kw_only=True)\nclass {}:\n",
+                if frozen { "frozen=True, " } else { "" },
+                id
+            )
+        }
+    }
+
+    /// Config of a pydantic model, which goes after its docstring. Models with
+    /// aliased fields may also be built with the field names.
+    fn model_config(&self, frozen: bool, aliased: bool, imports: &mut BTreeSet<String>) -> String {
+        let mut config = vec![];
+        if frozen {
+            config.push("frozen=True");
+        }
+        if aliased {
+            config.push("populate_by_name=True");
+        }
+        if !self.pydantic() || config.is_empty() {
+            return String::new();
+        }
+        imports.insert("from pydantic import ConfigDict\n".to_string());
+        format!("    model_config = ConfigDict({})\n\n", config.join(", "))
+    }
+
+    /// Field declaration of the property `name`. Fields named after a keyword keep
+    /// the wire name as alias, in the pydantic `Field` or the dataclass field
+    /// metadata.
+    fn field(
+        &self,
+        name: &str,
+        ty: &str,
+        default: Option<&str>,
+        imports: &mut BTreeSet<String>,
+    ) -> String {
+        let field_name = python_name(name);
+        let wire_name = ccase!(snake, name);
+        let value = if field_name == wire_name {
+            default.map_or(String::new(), |default| format!(" = {}", default))
+        } else {
+            let default = default.map_or(String::new(), |default| format!("default={}, ", default));
+            if self.pydantic() {
+                imports.insert("from pydantic import Field\n".to_string());
+                format!(" = Field({}alias=\"{}\")", default, wire_name)
+            } else {
+                imports.insert("from dataclasses import field\n".to_string());
+                format!(
+                    " = field({}metadata={{\"alias\": \"{}\"}})",
+                    default, wire_name
+                )
+            }
+        };
+        format!("    {}: {}{}\n", field_name, ty, value)
+    }
+
+    /// One frozen class per variant, tagged with `type` like the Rust enum, e.g.
+    /// `{ "type": "K_PAID", "amount": 10 }`, and the union of them.
+    fn gen_tagged_enum(
+        &self,
+        enumm: &Enum,
+        hashed: bool,
+        imports: &mut BTreeSet<String>,
+    ) -> String {
+        imports.insert("from typing import Literal\n".to_string());
+        imports.insert("from typing import Union\n".to_string());
+        let mut code = String::new();
+        let mut classes = vec![];
+        for variant in &enumm.variants {
+            let name = format!("{}{}", enumm.id, ccase!(pascal, variant.id()));
+            code.push_str(&self.class_header(&name, true, imports));
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("    \"\"\"{}\"\"\"\n\n", desc));
+            }
+            let fields = variant.fields().into_iter().flatten().collect::<Vec<_>>();
+            let aliased = fields
+                .iter()
+                .any(|(key, _)| python_name(key) != ccase!(snake, *key));
+            code.push_str(&self.model_config(true, aliased, imports));
+            for (key, value) in fields {
+                let ty = match parse_type(value) {
+                    Some(ty) => {
+                        type_imports(&ty, imports);
+                        if hashed {
+                            python_hashable_type(&ty)
+                        } else {
+                            python_type(&ty)
+                        }
+                    }
+                    None => value.clone(),
+                };
+                code.push_str(&self.field(key, &ty, None, imports));
+            }
+            let tag = python_string(variant.wire_value());
+            code.push_str(&format!("    type: Literal[{}] = {}\n\n\n", tag, tag));
+            classes.push(name);
+        }
+        if let Some(desc) = &enumm.description {
+            code.push_str(&format!("# {}\n", desc));
+        }
+        code.push_str(&format!("{} = Union[{}]\n", enumm.id, classes.join(", ")));
+        code
+    }
+}
+
+impl LangGenerator for PythonGenerator {
+    fn extension(&self) -> &str {
+        "py"
+    }
+
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut imports = BTreeSet::new();
+        if enumm.is_tagged() {
+            let hashed = udl.hashed_types().contains(enumm.id.as_str());
+            let code = self.gen_tagged_enum(enumm, hashed, &mut imports);
+            return (code, imports);
+        }
+        imports.insert("from enum import Enum\n".to_string());
+        // Errors of validated models are raised as they are
+        let error = udl
+            .models
+            .iter()
+            .any(|class| class.error.as_deref() == Some(enumm.id.as_str()));
+        let mut code = format!(
+            "class {}({}Enum):\n",
+            enumm.id,
+            if error { "Exception, " } else { "" }
+        );
+        if let Some(desc) = &enumm.description {
+            code.push_str(&format!("    \"\"\"{}\"\"\"\n\n", desc));
+        }
+        for variant in &enumm.variants {
+            code.push_str(&format!(
+                "    {} = {}\n",
+                variant.id(),
+                python_string(variant.wire_value())
+            ));
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("    \"\"\"{}\"\"\"\n", desc));
+            }
+        }
+        (code, imports)
+    }
+
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        // Set elements and map keys have to hash, which takes a frozen class
+        let hashed = udl.hashed_types().contains(class.id.as_str());
+        let frozen = class.immutable == Some(true) || hashed;
+        let mut code = self.class_header(&class.id, frozen, &mut imports);
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("    \"\"\"{}\"\"\"\n\n", desc));
+        }
+        let aliased = class
+            .properties
+            .keys()
+            .any(|name| python_name(name) != ccase!(snake, name));
+        let config = self.model_config(frozen, aliased, &mut imports);
+        code.push_str(&config);
+        let mut fields = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            let default = udl
+                .property_default(prop, &ty)
+                .and_then(|default| python_default(&default, &ty));
+            // Optional fields without a default may be left out
+            let (ty, default) = match default {
+                None if ty.optional => (
+                    TypeExpr {
+                        nullable: true,
+                        ..ty
+                    },
+                    Some("None".to_string()),
+                ),
+                default => (ty, default),
+            };
+            type_imports(&ty, &mut imports);
+            let field_type = if hashed {
+                python_hashable_type(&ty)
+            } else {
+                python_type(&ty)
+            };
+            code.push_str(&self.field(name, &field_type, default.as_deref(), &mut imports));
+            if let Some(desc) = prop.get(&PropertyKey::Description) {
+                code.push_str(&format!("    \"\"\"{}\"\"\"\n", desc));
+            }
+            fields.push((name, ty));
+        }
+        if fields.is_empty() && class.description.is_none() && config.is_empty() {
+            code.push_str("    pass\n");
+        }
+
+        if !is_validated(class) {
+            return Ok((code, imports));
+        }
+        let Some(error_enum) = error_enum else {
+            return Err(UdlError::MissingErrorEnum {
+                model: class.id.clone(),
+                error: class.error.clone().unwrap_or_default(),
+            });
+        };
+        let constraints = constraints(class, error_enum)?;
+        if constraints.iter().any(|c| c.check.pattern().is_some()) {
+            imports.insert("import re\n".to_string());
+        }
+        if self.pydantic() {
+            imports.insert("from pydantic import model_validator\n".to_string());
+            code.push_str(&format!(
+                "\n    @model_validator(mode=\"after\")\n    def _validate(self) -> {}:\n",
+                class.id
+            ));
+        } else {
+            code.push_str("\n    def __post_init__(self) -> None:\n");
+        }
+        for constraint in &constraints {
+            let (_, ty) = fields
+                .iter()
+                .find(|(name, _)| name.as_str() == constraint.property)
+                .unwrap();
+            code.push_str(&format!(
+                "        if {}:\n            raise {}.{}\n",
+                constraint_failure(constraint, &python_name(constraint.property), ty),
+                error_enum.id,
+                constraint.variant
+            ));
+        }
+        if self.pydantic() {
+            code.push_str("        return self\n");
+        }
+        Ok((code, imports))
+    }
+
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
+        imports.insert("from typing import Protocol\n".to_string());
+        let mut code = format!("class {}(Protocol):\n", interface.id);
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("    \"\"\"{}\"\"\"\n", desc));
+        }
+        for method in &interface.methods {
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => "None".to_string(),
+                Some(ReturnType::Type(ty)) => {
+                    type_imports(&ty, &mut imports);
+                    python_type(&ty)
+                }
+                Some(ReturnType::Result { ok, err }) => {
+                    for ty in ok.iter().chain([&err]) {
+                        type_imports(ty, &mut imports);
+                    }
+                    let ok = ok.as_ref().map_or("None".to_string(), python_type);
+                    format!("Result[{}, {}]", ok, python_type(&err))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            code.push_str(&format!(
+                "\n    {}def {}(self{}) -> {}:",
+                if method.is_async { "async " } else { "" },
+                python_name(method.id.as_str()),
+                method_params(&params, &mut imports),
+                returns
+            ));
+            match &method.description {
+                Some(desc) => {
+                    code.push_str(&format!("\n        \"\"\"{}\"\"\"\n        ...\n", desc))
+                }
+                None => code.push_str(" ...\n"),
+            }
+        }
+        if interface.methods.is_empty() && interface.description.is_none() {
+            code.push_str("    pass\n");
+        }
+        Ok((code, imports))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        for enum_def in &udl.enums {
+            let (gen_code, imports_) = self.gen_enum(udl, enum_def);
+            code.push_str(&gen_code);
+            code.push_str("\n\n");
+            imports.extend(imports_);
+        }
+
+        for class in &udl.models {
+            let error_enum = udl
+                .enums
+                .iter()
+                .find(|e| e.id == class.clone().error.unwrap_or_default());
+            let (gen_code, imports_) = self.gen_class(udl, class, error_enum)?;
+            code.push_str(&gen_code);
+            code.push_str("\n\n");
+            imports.extend(imports_);
+        }
+
+        for interface in &udl.interfaces {
+            let (gen_code, imports_) = self.gen_interface(interface)?;
+            code.push_str(&gen_code);
+            code.push_str("\n\n");
+            imports.extend(imports_);
+        }
+
+        if code.contains("Result[") {
+            code.insert_str(0, RESULT_TYPE);
+            for import in [
+                "from dataclasses import dataclass\n",
+                "from typing import Generic\n",
+                "from typing import TypeVar\n",
+                "from typing import Union\n",
+            ] {
+                imports.insert(import.to_string());
+            }
+        }
+
+        // Names imported from the same module share one `from` import, pydantic
+        // goes last as the only third-party module
+        let mut modules = BTreeMap::<(bool, &str), Vec<&str>>::new();
+        let mut plain = String::new();
+        for import in &imports {
+            match import.trim_end().strip_prefix("from ") {
+                Some(rest) => {
+                    let (module, name) = rest.split_once(" import ").unwrap_or((rest, ""));
+                    modules
+                        .entry((module == "pydantic", module))
+                        .or_default()
+                        .push(name);
+                }
+                None => plain.push_str(import),
+            }
+        }
+        let mut from = String::new();
+        for ((third_party, module), names) in modules {
+            if third_party {
+                from.push('\n');
+            }
+            from.push_str(&format!("from {} import {}\n", module, names.join(", ")));
+        }
+        Ok(format!(
+            "from __future__ import annotations\n\n{}{}\n\n{}",
+            plain,
+            from,
+            code.trim_end_matches('\n').to_string() + "\n"
+        ))
+    }
+
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "ruff".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        let status = std::process::Command::new("ruff")
+            .arg("format")
+            .arg(path)
+            .stdout(Stdio::piped())
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pydantic() -> PythonGenerator {
+        PythonGenerator(PythonOptions {
+            python_models: PythonModels::Pydantic,
+        })
+    }
+
+    #[test]
+    fn test_python_type() {
+        for (udl_type, expected) in [
+            ("int64", "int"),
+            ("uint8?", "Optional[int]"),
+            ("datetime^", "datetime"),
+            ("decimal", "Decimal"),
+            ("List<$enum::SortOrder>", "list[SortOrder]"),
+            (
+                "Map<string, Set<int?>>?",
+                "Optional[dict[str, set[Optional[int]]]]",
+            ),
+        ] {
+            assert_eq!(python_type(&parse_type(udl_type).unwrap()), expected);
+        }
+        assert_eq!(python_literal("2", "float"), Some("2.0".to_string()));
+        assert_eq!(python_literal("true", "bool"), Some("True".to_string()));
+        assert_eq!(python_regex(r"^\d+$"), "r\"^\\d+$\"");
+        assert_eq!(python_regex(r#"^"\w"$"#), "\"^\\\"\\\\w\\\"$\"");
+    }
+
+    #[test]
+    fn test_gen_enum() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let (code, imports) = PythonGenerator::default().gen_enum(&udl, find("PaymentStatus"));
+        assert!(code.starts_with(
+            "class PaymentStatus(Enum):\n    \
+             \"\"\"Status of an invoice\"\"\"\n\n    \
+             K_DRAFT = \"draft\"\n    \
+             \"\"\"Invoice not yet sent\"\"\"\n    \
+             K_SENT = \"sent\"\n"
+        ));
+        assert!(imports.contains("from enum import Enum\n"));
+        // Errors of validated models are raised
+        let (code, _) = PythonGenerator::default().gen_enum(&udl, find("LoginError"));
+        assert!(code.starts_with("class LoginError(Exception, Enum):\n"));
+
+        // Variants are classes told apart by their `Literal` tag, a keyword field keeps
+        // its wire name as alias
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Transfer
+            variants:
+              - id: K_DONE
+                value: "done"
+              - id: K_PENDING
+                value:
+                  from: string
+                  retryAt: datetime?
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = PythonGenerator::default().gen_enum(&udl, &enumm);
+        assert_eq!(
+            code,
+            "@dataclass(frozen=True, kw_only=True)\n\
+             class TransferKDone:\n    \
+             type: Literal[\"done\"] = \"done\"\n\n\n\
+             @dataclass(frozen=True, kw_only=True)\n\
+             class TransferKPending:\n    \
+             from_: str = field(metadata={\"alias\": \"from\"})\n    \
+             retry_at: Optional[datetime]\n    \
+             type: Literal[\"K_PENDING\"] = \"K_PENDING\"\n\n\n\
+             Transfer = Union[TransferKDone, TransferKPending]\n"
+        );
+        assert!(imports.contains("from dataclasses import field\n"));
+
+        let (code, imports) = pydantic().gen_enum(&udl, &enumm);
+        assert!(code.starts_with(
+            "class TransferKDone(BaseModel):\n    \
+             model_config = ConfigDict(frozen=True)\n\n"
+        ));
+        assert!(code.contains(
+            "class TransferKPending(BaseModel):\n    \
+             model_config = ConfigDict(frozen=True, populate_by_name=True)\n\n    \
+             from_: str = Field(alias=\"from\")\n"
+        ));
+        assert!(imports.contains("from pydantic import Field\n"));
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let generator = PythonGenerator::default();
+
+        let (code, imports) = generator
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert_eq!(
+            code,
+            "@dataclass(kw_only=True)\n\
+             class LoginRequest:\n    \
+             \"\"\"User login request\"\"\"\n\n    \
+             email: str\n    \
+             \"\"\"User email address\"\"\"\n    \
+             password: str\n    \
+             remember_me: bool = False\n\n    \
+             def __post_init__(self) -> None:\n        \
+             if not re.search(r\"^[^@\\s]+@[^@\\s]+\\.[^@\\s]+$\", self.email):\n            \
+             raise LoginError.K_INVALID_EMAIL\n        \
+             if len(self.password) < 8:\n            \
+             raise LoginError.K_INVALID_PASSWORD_MIN\n        \
+             if len(self.password) > 32:\n            \
+             raise LoginError.K_INVALID_PASSWORD_MAX\n"
+        );
+        assert!(imports.contains("import re\n"));
+
+        // pydantic validates after parsing, and the validator has to return the model
+        let (code, _) = pydantic()
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains(
+            "    @model_validator(mode=\"after\")\n    \
+             def _validate(self) -> CreateInvoiceRequest:\n"
+        ));
+        assert!(code.contains("        if self.notes is not None and len(self.notes) > 500:\n"));
+        assert!(code.ends_with("        return self\n"));
+
+        let mut class = find("FetchInvoicesRequest").clone();
+        class.immutable = Some(true);
+        let (code, _) = generator.gen_class(&udl, &class, None).unwrap();
+        assert!(code.starts_with("@dataclass(frozen=True, kw_only=True)\n"));
+        assert!(code.contains("    sort_order: SortOrder = SortOrder.K_DESC\n"));
+        let (code, _) = pydantic().gen_class(&udl, &class, None).unwrap();
+        assert!(code.contains("    model_config = ConfigDict(frozen=True)\n"));
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+
+        let code = pydantic().generate(&udl).unwrap();
+        assert!(code.starts_with("from __future__ import annotations\n\nimport re\n"));
+        assert!(code.contains("\nfrom pydantic import BaseModel, model_validator\n"));
+        assert_eq!(code.matches("class Ok(").count(), 1);
+    }
+
+    #[test]
+    fn test_hashed_models() {
+        let source = std::fs::read_to_string("tests/generated_rust/collections.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+
+        // `set[Label]` needs hashable labels, whose lists become tuples
+        let (code, _) = PythonGenerator::default()
+            .gen_class(&udl, &udl.models[0], None)
+            .unwrap();
+        assert!(code.starts_with("@dataclass(frozen=True, kw_only=True)\nclass Label:\n"));
+        assert!(code.contains("    color: Optional[tuple[int, ...]]\n"));
+        let (code, _) = pydantic().gen_class(&udl, &udl.models[0], None).unwrap();
+        assert!(code.contains("    model_config = ConfigDict(frozen=True)\n"));
+
+        let (code, _) = PythonGenerator::default()
+            .gen_class(&udl, &udl.models[1], None)
+            .unwrap();
+        assert!(code.starts_with("@dataclass(kw_only=True)\nclass Board:\n"));
+        assert!(code.contains("    labels: set[Label]\n"));
+        assert!(code.contains("    by_name: dict[str, list[Label]]\n"));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Lesson
+            properties:
+              class: string
+              is: bool
+            "#,
+        )
+        .unwrap();
+        let (code, imports) = PythonGenerator::default()
+            .gen_class(&udl, &class, None)
+            .unwrap();
+        assert!(code.contains(
+            "    class_: str = field(metadata={\"alias\": \"class\"})\n    \
+             is_: bool = field(default=False, metadata={\"alias\": \"is\"})\n"
+        ));
+        assert!(imports.contains("from dataclasses import field\n"));
+        let (code, _) = pydantic().gen_class(&udl, &class, None).unwrap();
+        assert!(code.contains(
+            "    model_config = ConfigDict(populate_by_name=True)\n\n    \
+             class_: str = Field(alias=\"class\")\n    \
+             is_: bool = Field(default=False, alias=\"is\")\n"
+        ));
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, _) = PythonGenerator::default()
+            .gen_interface(&interface)
+            .unwrap();
+        assert_eq!(
+            code,
+            "class InvoiceService(Protocol):\n    \
+             \"\"\"Invoice management operations\"\"\"\n\n    \
+             async def fetch_invoices(self, fetch_invoices_request: FetchInvoicesRequest) -> Result[Invoice, InvoiceStatus]: ...\n\n    \
+             def get_invoice(self, id: str, include_items: Optional[bool]) -> Optional[Invoice]:\n        \
+             \"\"\"Get a specific invoice by ID\"\"\"\n        \
+             ...\n\n    \
+             async def delete_invoice(self, value: str) -> Result[None, ApiError]: ...\n\n    \
+             def ping(self) -> None: ...\n"
+        );
+    }
+}
@@ -1,17 +1,121 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     process::Stdio,
     sync::LazyLock,
 };
 
+use clap::{Args, ValueEnum};
+use convert_case::ccase;
+
 use crate::udl::{
-    LangGenerator, UDL,
-    class::{Class, Property, PropertyKey},
-    enums::{Enum, EnumKind, EnumVariantValue},
-    utils::is_nullable_type,
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    core::TypeDefault,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::parse_type,
 };
 
-pub(crate) struct RustGenerator();
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RustGenerator(pub RustOptions);
+
+/// Rust specific options of `udl generate`.
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct RustOptions {
+    /// Generate `BTreeSet` and `BTreeMap` instead of `HashSet` and `HashMap`
+    #[arg(long)]
+    pub rust_ordered_collections: bool,
+
+    /// Crate used for `datetime` and `date` fields
+    #[arg(long, value_enum, default_value_t)]
+    pub rust_datetime: DateTimeCrate,
+
+    /// Case of struct field names on the wire. The default matches Dart's `toJson`.
+    #[arg(long, value_enum, default_value_t)]
+    pub rust_rename_all: RenameRule,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum DateTimeCrate {
+    /// `chrono::DateTime<Utc>` and `chrono::NaiveDate`
+    #[default]
+    Chrono,
+    /// `time::OffsetDateTime` and `time::Date`
+    Time,
+}
+
+/// Values of serde's `rename_all` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum RenameRule {
+    #[value(name = "lowercase")]
+    Lower,
+    #[value(name = "UPPERCASE")]
+    Upper,
+    #[value(name = "PascalCase")]
+    Pascal,
+    #[value(name = "camelCase")]
+    Camel,
+    #[default]
+    #[value(name = "snake_case")]
+    Snake,
+    #[value(name = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnake,
+    #[value(name = "kebab-case")]
+    Kebab,
+    #[value(name = "SCREAMING-KEBAB-CASE")]
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Wire name serde gives to the snake_case field `name` under this rule.
+    fn apply(&self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => name.to_ascii_uppercase(),
+            RenameRule::Pascal => ccase!(pascal, name),
+            RenameRule::Camel => ccase!(camel, name),
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => name.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Keywords of all editions, usable as raw identifiers such as `r#type`. Serde
+/// strips the `r#` from wire names.
+const KEYWORDS: [&str; 48] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers, renamed with a trailing `_` instead.
+const RESERVED: [&str; 3] = ["self", "super", "crate"];
+
+/// snake_case Rust name of a field, param or method, raw when it is a keyword.
+fn rust_name(name: &str) -> String {
+    let name = ccase!(snake, name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else if RESERVED.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// Serde module of `time::Date` as `yyyy-MM-dd`, the format of Dart's `date` fields.
+const DATE_FORMAT: &str =
+    "time::serde::format_description!(iso_date, Date, \"[year]-[month]-[day]\");";
 
 static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
     HashMap::from([
@@ -31,28 +135,329 @@ static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
         ("uint64", "u64"),
         ("float32", "f32"),
         ("float64", "f64"),
+        ("bytes", "Vec<u8>"),
+        ("email", "String"),
     ])
 });
 
-fn clean_type_name(type_name: &str) -> String {
-    type_name
-        .replace("?", "")
-        .replace("^", "")
-        .replace("$enum::", "")
+impl RustGenerator {
+    /// Derives of the enum or model `id`. Set elements and map keys, and the types
+    /// of their fields, also need `Eq + Hash`, or `Ord` for ordered collections.
+    fn derives(&self, udl: &UDL, id: &str) -> String {
+        let mut derives = vec!["Debug", "Clone", "PartialEq"];
+        if udl.hashed_types().contains(id) {
+            derives.extend(["Eq", "Hash"]);
+            if self.0.rust_ordered_collections {
+                derives.extend(["PartialOrd", "Ord"]);
+            }
+        }
+        derives.extend(["Serialize", "Deserialize"]);
+        format!("#[derive({})]\n", derives.join(", "))
+    }
+
+    /// Rust type of a primitive. Types backed by a crate add its `use` line to `imports`.
+    fn rust_primitive(&self, name: &str, imports: &mut BTreeSet<String>) -> String {
+        let (ty, import) = match (name, self.0.rust_datetime) {
+            ("datetime", DateTimeCrate::Chrono) => {
+                ("DateTime<Utc>", "use chrono::{DateTime, Utc};")
+            }
+            ("date", DateTimeCrate::Chrono) => ("NaiveDate", "use chrono::NaiveDate;"),
+            ("datetime", DateTimeCrate::Time) => ("OffsetDateTime", "use time::OffsetDateTime;"),
+            ("date", DateTimeCrate::Time) => ("Date", "use time::Date;"),
+            ("object", _) => ("Value", "use serde_json::Value;"),
+            ("uuid", _) => ("Uuid", "use uuid::Uuid;"),
+            ("decimal", _) => ("Decimal", "use rust_decimal::Decimal;"),
+            ("url", _) => ("Url", "use url::Url;"),
+            ("duration", _) => ("Duration", "use std::time::Duration;"),
+            _ => return MAPPINGS.get(name).copied().unwrap_or(name).to_string(),
+        };
+        imports.insert(format!("{}\n", import));
+        ty.to_string()
+    }
+
+    fn rust_type(&self, ty: &TypeExpr, imports: &mut BTreeSet<String>) -> String {
+        let mut collection = |hashed: &str, ordered: &str| {
+            let name = if self.0.rust_ordered_collections {
+                ordered
+            } else {
+                hashed
+            };
+            imports.insert(format!("use std::collections::{};\n", name));
+            name.to_string()
+        };
+        let name = match &ty.kind {
+            TypeKind::Primitive(name) => self.rust_primitive(name, imports),
+            TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+            TypeKind::List(_) => "Vec".to_string(),
+            TypeKind::Set(_) => collection("HashSet", "BTreeSet"),
+            TypeKind::Map(..) => collection("HashMap", "BTreeMap"),
+            TypeKind::Generic { name, .. } => name.clone(),
+            TypeKind::Void => "()".to_string(),
+        };
+        let name = match ty.args()[..] {
+            [] => name,
+            ref args => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(|arg| self.rust_type(arg, imports))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        if ty.nullable {
+            format!("Option<{}>", name)
+        } else {
+            name
+        }
+    }
+
+    /// `serde_with` adapter of types whose wire format differs from their serde
+    /// representation: `bytes` as base64 and `duration` as milliseconds. `None` when
+    /// the type and all of its arguments serialize as is.
+    fn serde_as(&self, ty: &TypeExpr, imports: &mut BTreeSet<String>) -> Option<String> {
+        let adapter = match &ty.kind {
+            TypeKind::Primitive(name) if name == "bytes" => {
+                imports.insert("use serde_with::base64::Base64;\n".to_string());
+                "Base64".to_string()
+            }
+            TypeKind::Primitive(name) if name == "duration" => {
+                imports.insert("use serde_with::DurationMilliSeconds;\n".to_string());
+                "DurationMilliSeconds<u64>".to_string()
+            }
+            TypeKind::List(_) | TypeKind::Set(_) | TypeKind::Map(..) => {
+                let args = ty
+                    .args()
+                    .into_iter()
+                    .map(|arg| self.serde_as(arg, imports))
+                    .collect::<Vec<_>>();
+                if args.iter().all(Option::is_none) {
+                    return None;
+                }
+                let name = match (&ty.kind, self.0.rust_ordered_collections) {
+                    (TypeKind::List(_), _) => "Vec",
+                    (TypeKind::Set(_), false) => "HashSet",
+                    (TypeKind::Set(_), true) => "BTreeSet",
+                    (_, false) => "HashMap",
+                    (_, true) => "BTreeMap",
+                };
+                format!(
+                    "{}<{}>",
+                    name,
+                    args.into_iter()
+                        .map(|arg| arg.unwrap_or_else(|| "_".to_string()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            _ => return None,
+        };
+        if ty.nullable {
+            Some(format!("Option<{}>", adapter))
+        } else {
+            Some(adapter)
+        }
+    }
+
+    /// Field type and attributes of a property. Fields with a `default_fn` fall back
+    /// to it, other optional fields to `Default` when the type has one, and to
+    /// `Option` otherwise. Like Dart, which has no empty `Uint8List` constant,
+    /// optional `bytes` are an `Option`.
+    fn rust_field(
+        &self,
+        udl: &UDL,
+        ty: &TypeExpr,
+        default_fn: Option<&str>,
+        imports: &mut BTreeSet<String>,
+    ) -> (String, Vec<String>) {
+        let (has_default, has_is_empty) = match &ty.kind {
+            TypeKind::Primitive(name) => (
+                MAPPINGS.contains_key(name.as_str()) && name != "bytes",
+                matches!(name.as_str(), "string" | "email"),
+            ),
+            TypeKind::Enum(_) => (udl.type_default(ty).is_some(), false),
+            TypeKind::List(_) | TypeKind::Set(_) | TypeKind::Map(..) => (true, true),
+            TypeKind::Model(_) | TypeKind::Generic { .. } | TypeKind::Void => (false, false),
+        };
+        let (ty, mut attrs) = if let Some(default_fn) = default_fn {
+            (
+                ty.clone(),
+                vec![format!("#[serde(default = \"{}\")]", default_fn)],
+            )
+        } else if !ty.optional {
+            (ty.clone(), vec![])
+        } else if has_default && !ty.nullable {
+            let attr = if has_is_empty {
+                let field_type = self.rust_type(ty, imports);
+                format!(
+                    "#[serde(default, skip_serializing_if = \"{}::is_empty\")]",
+                    field_type.split('<').next().unwrap_or_default()
+                )
+            } else {
+                "#[serde(default)]".to_string()
+            };
+            (ty.clone(), vec![attr])
+        } else {
+            let ty = TypeExpr {
+                nullable: true,
+                ..ty.clone()
+            };
+            let attr = "#[serde(default, skip_serializing_if = \"Option::is_none\")]";
+            (ty, vec![attr.to_string()])
+        };
+        if let Some(adapter) = self.serde_as(&ty, imports) {
+            attrs.push(format!("#[serde_as(as = \"{}\")]", adapter));
+        }
+        attrs.extend(self.serde_with(&ty, imports));
+        (self.rust_type(&ty, imports), attrs)
+    }
+
+    /// `with` module of `time` values, whose own serde format is not a string:
+    /// RFC 3339 for `datetime` and `yyyy-MM-dd` for `date`. The date format module is
+    /// declared next to the imports.
+    fn serde_with(&self, ty: &TypeExpr, imports: &mut BTreeSet<String>) -> Option<String> {
+        let module = match &ty.kind {
+            _ if self.0.rust_datetime != DateTimeCrate::Time => return None,
+            TypeKind::Primitive(name) if name == "datetime" => "time::serde::rfc3339",
+            TypeKind::Primitive(name) if name == "date" => {
+                imports.insert(format!("{}\n", DATE_FORMAT));
+                "iso_date"
+            }
+            _ => return None,
+        };
+        Some(if ty.nullable {
+            format!("#[serde(with = \"{}::option\")]", module)
+        } else {
+            format!("#[serde(with = \"{}\")]", module)
+        })
+    }
+
+    /// `rename` attribute of a field whose name is a keyword that can't be raw, e.g.
+    /// `self_` for `self`.
+    fn rename_attr(&self, name: &str) -> Option<String> {
+        let name = ccase!(snake, name);
+        RESERVED.contains(&name.as_str()).then(|| {
+            format!(
+                "#[serde(rename = \"{}\")]",
+                self.0.rust_rename_all.apply(&name)
+            )
+        })
+    }
+
+    /// Arguments of a trait method, starting with `&self`.
+    fn method_params(
+        &self,
+        params: &[(Option<&str>, TypeExpr)],
+        imports: &mut BTreeSet<String>,
+    ) -> String {
+        std::iter::once("&self".to_string())
+            .chain(params.iter().map(|(name, ty)| {
+                let name = rust_name(param_name(*name, ty));
+                format!("{}: {}", name, self.rust_type(ty, imports))
+            }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Scalars compared by value in validated constructors and returned by value from
+/// getters.
+const NUMBER_TYPES: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+];
+
+fn is_copy(field_type: &str) -> bool {
+    let inner = field_type
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'))
+        .unwrap_or(field_type);
+    inner == "bool" || NUMBER_TYPES.contains(&inner)
+}
+
+/// Rust identifier of an enum variant, e.g. `KLoggedIn` for `K_LOGGED_IN`.
+fn variant_ident(id: &str) -> String {
+    ccase!(pascal, id)
+}
+
+/// Expression of a default value, already checked against `ty` by the validator,
+/// which only accepts defaults of strings, bools, numbers and enums.
+fn rust_default(default: &TypeDefault, ty: &TypeExpr) -> String {
+    let value = match default {
+        TypeDefault::Variant(enumm, variant) => {
+            format!("{}::{}", enumm.id, variant_ident(variant.id()))
+        }
+        TypeDefault::Value(value) if value == "null" => return "None".to_string(),
+        TypeDefault::Value(value) => match &ty.kind {
+            TypeKind::Primitive(name) if matches!(name.as_str(), "string" | "email") => {
+                format!("{:?}.to_string()", value)
+            }
+            TypeKind::Primitive(name)
+                if matches!(name.as_str(), "float" | "float32" | "float64")
+                    && !value.contains(['.', 'e', 'E']) =>
+            {
+                format!("{}.0", value)
+            }
+            _ => value.to_string(),
+        },
+    };
+    if ty.nullable {
+        format!("Some({})", value)
+    } else {
+        value
+    }
 }
 
-fn process_type(type_name: &str) -> String {
-    let is_nullable = is_nullable_type(type_name);
-    let name = clean_type_name(type_name);
-    let mapped_type = MAPPINGS.get(name.as_str());
-    let prefix = if is_nullable { "Option<" } else { "" };
-    let suffix = if is_nullable { ">" } else { "" };
-    format!(
-        "{}{}{}",
-        prefix,
-        mapped_type.unwrap_or(&name.as_str()),
-        suffix
-    )
+/// Name of the `Regex` static a format or pattern constraint is checked with.
+fn regex_static(constraint: &Constraint) -> String {
+    let suffix = match constraint.check {
+        Check::Pattern(_) => "PATTERN",
+        _ => "FORMAT",
+    };
+    format!("{}_{}", ccase!(constant, constraint.property), suffix)
+}
+
+/// Condition under which `constraint` fails for the argument `name`. Limits compare
+/// numbers by value, strings by their number of chars, which are code points, and
+/// anything else by `len()`. Patterns match the value as a string. A missing value
+/// passes.
+fn constraint_failure(constraint: &Constraint, name: &str, field_type: &str) -> String {
+    let (inner, nullable) = match field_type
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'))
+    {
+        Some(inner) => (inner, true),
+        None => (field_type, false),
+    };
+    let check = match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            match inner {
+                "f32" | "f64" => format!("{} {} {}.0", name, operator, limit),
+                inner if NUMBER_TYPES.contains(&inner) => {
+                    format!("{} {} {}", name, operator, limit)
+                }
+                "String" => format!("{}.chars().count() {} {}", name, operator, limit),
+                _ => format!("{}.len() {} {}", name, operator, limit),
+            }
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            // Optional strings are already borrowed inside `is_some_and`
+            let value = match (inner, nullable) {
+                ("String", true) => name.to_string(),
+                ("String", false) => format!("&{}", name),
+                _ => format!("&{}.to_string()", name),
+            };
+            format!("!{}.is_match({})", regex_static(constraint), value)
+        }
+    };
+    match (nullable, is_copy(inner)) {
+        (false, _) => check,
+        (true, true) => format!("{}.is_some_and(|{}| {})", name, name, check),
+        (true, false) => format!("{}.as_ref().is_some_and(|{}| {})", name, name, check),
+    }
 }
 
 impl LangGenerator for RustGenerator {
@@ -60,87 +465,379 @@ impl LangGenerator for RustGenerator {
         "rs"
     }
 
-    fn gen_enum(&self, enumm: &Enum) -> String {
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut imports = BTreeSet::new();
         let mut code = String::new();
         if let Some(desc) = enumm.description.as_ref() {
             code.push_str(&format!("/// {}\n", desc));
         }
-        code.push_str("enum ");
-        code.push_str(&enumm.id);
-        code.push_str(" {");
+        let mut variants = String::new();
         for variant in &enumm.variants {
-            //Handle default value
-            match variant {
-                EnumKind::Simple(name) => {
-                    code.push_str(&format!("{},", name));
-                }
-                EnumKind::Complex(variant) => {
-                    if let Some(desc) = variant.description.as_ref() {
-                        code.push_str(&format!("/// {}\n", desc));
-                    }
-                    code.push_str(&format!("{}", variant.id));
-
-                    match &variant.value {
-                        EnumVariantValue::Single(_) => {}
-                        EnumVariantValue::Multiple(map) => {
-                            code.push_str(" {");
-                            for (key, value) in map {
-                                if MAPPINGS.contains_key(value.as_str()) {
-                                    code.push_str(&format!(
-                                        "{}: {},",
-                                        key,
-                                        MAPPINGS[value.as_str()]
-                                    ));
-                                } else {
-                                    code.push_str(&format!("{}: {},", key, value));
-                                }
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                variants.push_str(&format!("/// {}\n", desc));
+            }
+            // Same wire value as the Dart enum's `value`
+            variants.push_str(&format!(
+                "#[serde(rename = \"{}\")]\n{}",
+                variant
+                    .wire_value()
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\""),
+                variant_ident(variant.id())
+            ));
+            if let Some(map) = variant.fields() {
+                variants.push_str(" {");
+                for (key, value) in map {
+                    let ty = match parse_type(value) {
+                        Some(ty) => {
+                            if let Some(adapter) = self.serde_as(&ty, &mut imports) {
+                                variants.push_str(&format!("#[serde_as(as = \"{}\")]\n", adapter));
+                            }
+                            if let Some(attr) = self.serde_with(&ty, &mut imports) {
+                                variants.push_str(&format!("{}\n", attr));
                             }
-                            code.push_str("}");
+                            self.rust_type(&ty, &mut imports)
                         }
+                        None => value.clone(),
+                    };
+                    if let Some(attr) = self.rename_attr(key) {
+                        variants.push_str(&format!("{}\n", attr));
                     }
-                    code.push_str(",");
+                    variants.push_str(&format!("{}: {},", rust_name(key), ty));
                 }
+                variants.push('}');
             }
+            variants.push(',');
         }
-        code.push_str("}");
-        code
+        if variants.contains("#[serde_as(") {
+            imports.insert("use serde_with::serde_as;\n".to_string());
+            code.push_str("#[serde_as]\n");
+        }
+        code.push_str(&self.derives(udl, &enumm.id));
+        // Variants with fields are internally tagged by their wire value, e.g.
+        // `{ "type": "K_PAID", "amount": 10 }`
+        if enumm.is_tagged() {
+            code.push_str(&format!(
+                "#[serde(tag = \"type\", rename_all_fields = \"{}\")]\n",
+                self.0.rust_rename_all.name()
+            ));
+        }
+        code.push_str(&format!("pub enum {} {{{}", enumm.id, variants));
+        code.push('}');
+
+        if let Some(variant) = udl.enum_default(enumm) {
+            code.push_str(&format!(
+                "\n\nimpl Default for {} {{ fn default() -> Self {{ Self::{} }} }}",
+                enumm.id,
+                variant_ident(variant.id())
+            ));
+        }
+        // Errors of validated models fail their decoding, which needs `Display`
+        if udl
+            .models
+            .iter()
+            .any(|class| class.error.as_deref() == Some(enumm.id.as_str()))
+        {
+            code.push_str(&format!(
+                "\n\nimpl std::fmt::Display for {} {{ \
+                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{ \
+                 f.write_str(match self {{ {} }}) }} }}\n\n\
+                 impl std::error::Error for {} {{}}",
+                enumm.id,
+                enumm
+                    .variants
+                    .iter()
+                    .map(|variant| format!(
+                        "Self::{} {{ .. }} => {:?},",
+                        variant_ident(variant.id()),
+                        variant.wire_value()
+                    ))
+                    .collect::<String>(),
+                enumm.id
+            ));
+        }
+        (code, imports)
     }
 
-    fn gen_class(&self, class: &Class, _error_enum: Option<&Enum>) -> (String, HashSet<String>) {
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
         let mut code = String::new();
         if let Some(desc) = &class.description {
             code.push_str(&format!("/// {}\n", desc));
         }
-        code.push_str(&format!("pub struct {} {{", class.id));
-        // code.push_str("\n");
-        for (name, ty) in &class.properties {
-            match ty {
-                Property::Type(ty) => {
-                    code.push_str(&format!("    pub {}: {},", name, process_type(ty)));
-                }
-                Property::Map(map) => {
-                    if let Some(desc) = &map.get(&PropertyKey::Description) {
-                        code.push_str(&format!("/// {}\n", desc));
-                    }
-                    let private = map.get(&PropertyKey::Private) == Some(&String::from("true"));
-                    code.push_str(&format!(
-                        "    {} {}: {},",
-                        if private { "" } else { "pub" },
-                        name,
-                        process_type(&map[&PropertyKey::Type])
+        // Validated models are only built through `build()`, so all their fields are
+        // private
+        let validated = is_validated(class);
+        let mut fields = String::new();
+        let mut field_types = vec![];
+        // `Default` value of each field, when it has one
+        let mut field_defaults = vec![];
+        let mut default_fns = String::new();
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            let default = udl.property_default(prop, &ty);
+            let default_fn = format!("default_{}", ccase!(snake, name));
+            let (field_type, attrs) = self.rust_field(
+                udl,
+                &ty,
+                default
+                    .as_ref()
+                    .map(|_| format!("{}::{}", class.id, default_fn))
+                    .as_deref(),
+                &mut imports,
+            );
+            if let Some(default) = &default {
+                default_fns.push_str(&format!(
+                    "pub fn {}() -> {} {{ {} }}\n\n",
+                    default_fn,
+                    field_type,
+                    rust_default(default, &ty)
+                ));
+                field_defaults.push(Some(format!("Self::{}()", default_fn)));
+            } else if ty.nullable || attrs.iter().any(|attr| attr.starts_with("#[serde(default")) {
+                field_defaults.push(Some("Default::default()".to_string()));
+            } else {
+                field_defaults.push(None);
+            }
+            if let Some(desc) = prop.get(&PropertyKey::Description) {
+                fields.push_str(&format!("/// {}\n", desc));
+            }
+            for attr in self.rename_attr(name).into_iter().chain(attrs) {
+                fields.push_str(&format!("    {}\n", attr));
+            }
+            let public = !validated && prop.get(&PropertyKey::Private) != Some("true");
+            fields.push_str(&format!(
+                "    {} {}: {},",
+                if public { "pub" } else { "" },
+                rust_name(name),
+                field_type
+            ));
+            field_types.push((name, field_type, public));
+        }
+        if fields.contains("#[serde_as(") {
+            imports.insert("use serde_with::serde_as;\n".to_string());
+            code.push_str("#[serde_as]\n");
+        }
+        code.push_str(&self.derives(udl, &class.id));
+        // Validated models are decoded into their raw fields and checked by `build()`
+        if validated {
+            code.push_str(&format!("#[serde(try_from = \"{}Raw\")]\n", class.id));
+        }
+        code.push_str(&format!(
+            "#[serde(rename_all = \"{}\")]\n",
+            self.0.rust_rename_all.name()
+        ));
+        code.push_str(&format!("pub struct {} {{{}}}\n", class.id, fields));
+        if validated {
+            code.push_str(&format!(
+                "\n/// Fields of a [`{id}`] as decoded, before `build()` checks them\n\
+                 {serde_as}#[derive(Deserialize)]\n\
+                 #[serde(rename_all = \"{rename_all}\")]\n\
+                 struct {id}Raw {{{fields}}}\n\n\
+                 impl TryFrom<{id}Raw> for {id} {{ type Error = {error}; \
+                 fn try_from(raw: {id}Raw) -> Result<Self, Self::Error> {{ \
+                 Self::build({args}) }} }}\n",
+                id = class.id,
+                serde_as = if fields.contains("#[serde_as(") {
+                    "#[serde_as]\n"
+                } else {
+                    ""
+                },
+                rename_all = self.0.rust_rename_all.name(),
+                fields = fields,
+                error = class.error.as_deref().unwrap_or_default(),
+                args = field_types
+                    .iter()
+                    .map(|(name, _, _)| format!("raw.{}", rust_name(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let has_default_fns = !default_fns.is_empty();
+        let mut methods = default_fns;
+        if validated {
+            let Some(error_enum) = error_enum else {
+                return Err(UdlError::MissingErrorEnum {
+                    model: class.id.clone(),
+                    error: class.error.clone().unwrap_or_default(),
+                });
+            };
+            if field_types.len() > 7 {
+                methods.push_str("#[allow(clippy::too_many_arguments)]\n");
+            }
+            methods.push_str(&format!(
+                "pub fn build({}) -> Result<Self, {}> {{",
+                field_types
+                    .iter()
+                    .map(|(name, field_type, _)| format!("{}: {}", rust_name(name), field_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                error_enum.id
+            ));
+            for constraint in constraints(class, error_enum)? {
+                let (_, field_type, _) = field_types
+                    .iter()
+                    .find(|(name, _, _)| name.as_str() == constraint.property)
+                    .unwrap();
+                if let Some(pattern) = constraint.check.pattern() {
+                    imports.insert("use regex::Regex;\n".to_string());
+                    imports.insert("use std::sync::LazyLock;\n".to_string());
+                    let literal = if pattern.contains('"') {
+                        format!("{:?}", pattern)
+                    } else {
+                        format!("r\"{}\"", pattern)
+                    };
+                    methods.push_str(&format!(
+                        "static {}: LazyLock<Regex> = LazyLock::new(|| Regex::new({}).unwrap());\n",
+                        regex_static(&constraint),
+                        literal
                     ));
                 }
+                methods.push_str(&format!(
+                    "if {} {{ return Err({}::{}); }}\n",
+                    constraint_failure(&constraint, &rust_name(constraint.property), field_type),
+                    error_enum.id,
+                    variant_ident(constraint.variant)
+                ));
             }
+            methods.push_str(&format!(
+                "Ok(Self {{ {} }}) }}\n\n",
+                field_types
+                    .iter()
+                    .map(|(name, _, _)| rust_name(name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        for (name, field_type, public) in &field_types {
+            if *public {
+                continue;
+            }
+            let name = rust_name(name);
+            let (ret, body) = match field_type.split_once('<') {
+                _ if is_copy(field_type) => (field_type.clone(), format!("self.{}", name)),
+                None if field_type == "String" => ("&str".to_string(), format!("&self.{}", name)),
+                Some(("Vec", item)) => (
+                    format!("&[{}]", item.strip_suffix('>').unwrap_or(item)),
+                    format!("&self.{}", name),
+                ),
+                Some(("Option", "String>")) => (
+                    "Option<&str>".to_string(),
+                    format!("self.{}.as_deref()", name),
+                ),
+                Some(("Option", inner)) => (
+                    format!("Option<&{}", inner),
+                    format!("self.{}.as_ref()", name),
+                ),
+                _ => (format!("&{}", field_type), format!("&self.{}", name)),
+            };
+            methods.push_str(&format!(
+                "pub fn {}(&self) -> {} {{ {} }}\n\n",
+                name, ret, body
+            ));
+        }
+        if !methods.is_empty() {
+            code.push_str(&format!("\nimpl {} {{{}}}\n", class.id, methods));
         }
-        code.push_str(&format!("}}\n"));
-        (code, HashSet::new())
+        // Validated models are only built through `build()`, others get a `Default`
+        // when their own defaults cover every required field
+        if !validated
+            && has_default_fns
+            && let Some(defaults) = field_defaults.into_iter().collect::<Option<Vec<_>>>()
+        {
+            code.push_str(&format!(
+                "\nimpl Default for {} {{ fn default() -> Self {{ Self {{ {} }} }} }}\n",
+                class.id,
+                field_types
+                    .iter()
+                    .zip(defaults)
+                    .map(|((name, _, _), default)| format!("{}: {}", rust_name(name), default))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        Ok((code, imports))
     }
 
-    fn generate(&self, udl: &UDL) -> String {
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut imports = BTreeSet::new();
         let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        if interface.methods.iter().any(|method| method.is_async) {
+            code.push_str("#[allow(async_fn_in_trait)]\n");
+        }
+        code.push_str(&format!("pub trait {} {{\n", interface.id));
+        for method in &interface.methods {
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => String::new(),
+                Some(ReturnType::Type(ty)) => format!(" -> {}", self.rust_type(&ty, &mut imports)),
+                Some(ReturnType::Result { ok, err }) => {
+                    let ok = ok
+                        .as_ref()
+                        .map_or("()".to_string(), |ok| self.rust_type(ok, &mut imports));
+                    format!(" -> Result<{}, {}>", ok, self.rust_type(&err, &mut imports))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            code.push_str(&format!(
+                "    {}fn {}({}){};\n",
+                if method.is_async { "async " } else { "" },
+                rust_name(&method.id),
+                self.method_params(&params, &mut imports),
+                returns
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, imports))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut imports = BTreeSet::new();
+        let mut code = String::new();
+        if !udl.enums.is_empty() || !udl.models.is_empty() {
+            imports.insert("use serde::{Deserialize, Serialize};\n".to_string());
+        }
         for enum_def in &udl.enums {
-            code.push_str(&self.gen_enum(enum_def));
+            let (gen_code, imports_) = self.gen_enum(udl, enum_def);
+            code.push_str(&gen_code);
             code.push_str("\n\n");
+            imports.extend(imports_);
         }
 
         for class in &udl.models {
@@ -149,18 +846,653 @@ impl LangGenerator for RustGenerator {
                 .iter()
                 .find(|e| e.id == class.clone().error.unwrap_or_default());
 
-            code.push_str(&self.gen_class(class, error_enum).0);
+            let (gen_code, imports_) = self.gen_class(udl, class, error_enum)?;
+            code.push_str(&gen_code);
+            code.push_str("\n\n");
+            imports.extend(imports_);
+        }
+
+        for interface in &udl.interfaces {
+            let (gen_code, imports_) = self.gen_interface(interface)?;
+            code.push_str(&gen_code);
             code.push_str("\n\n");
+            imports.extend(imports_);
         }
 
-        code
+        Ok(format!(
+            "{}\n\n{}",
+            imports.into_iter().collect::<Vec<_>>().join("\n"),
+            code
+        ))
     }
 
-    fn format(&self, path: &str) {
-        std::process::Command::new("rustfmt")
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "rustfmt".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        // Native `async fn` in traits needs an edition newer than rustfmt's default
+        let status = std::process::Command::new("rustfmt")
+            .args(["--edition", "2021"])
             .arg(path)
             .stdout(Stdio::piped())
-            .spawn()
-            .expect("");
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let generate = || {
+            let udl: UDL = serde_yaml::from_str(&source).unwrap();
+            RustGenerator::default().generate(&udl).unwrap()
+        };
+        let code = generate();
+        for _ in 0..5 {
+            assert_eq!(generate(), code);
+        }
+
+        // Struct fields follow declaration order
+        let positions = ["email: String,", "password: String,", "remember_me: bool,"]
+            .iter()
+            .map(|needle| code.find(needle).unwrap())
+            .collect::<Vec<_>>();
+        assert!(positions.is_sorted(), "{:?}", positions);
+    }
+
+    #[test]
+    fn test_rust_type() {
+        let rust = |generator: &RustGenerator, value| {
+            let mut imports = BTreeSet::new();
+            let ty = generator.rust_type(&parse_type(value).unwrap(), &mut imports);
+            (ty, imports.into_iter().collect::<Vec<_>>())
+        };
+        let generator = RustGenerator::default();
+        assert_eq!(
+            rust(&generator, "uint8?"),
+            ("Option<u8>".to_string(), vec![])
+        );
+        assert_eq!(
+            rust(&generator, "List<string?>"),
+            ("Vec<Option<String>>".to_string(), vec![])
+        );
+        assert_eq!(
+            rust(&generator, "Map<string, Set<$enum::InvoiceStatus>?>?^"),
+            (
+                "Option<HashMap<String, Option<HashSet<InvoiceStatus>>>>".to_string(),
+                vec![
+                    "use std::collections::HashMap;\n".to_string(),
+                    "use std::collections::HashSet;\n".to_string()
+                ]
+            )
+        );
+
+        assert_eq!(
+            rust(&generator, "List<datetime>"),
+            (
+                "Vec<DateTime<Utc>>".to_string(),
+                vec!["use chrono::{DateTime, Utc};\n".to_string()]
+            )
+        );
+        assert_eq!(
+            rust(&generator, "Map<string, object>?"),
+            (
+                "Option<HashMap<String, Value>>".to_string(),
+                vec![
+                    "use serde_json::Value;\n".to_string(),
+                    "use std::collections::HashMap;\n".to_string()
+                ]
+            )
+        );
+
+        let time = RustGenerator(RustOptions {
+            rust_datetime: DateTimeCrate::Time,
+            ..Default::default()
+        });
+        assert_eq!(
+            rust(&time, "date?"),
+            (
+                "Option<Date>".to_string(),
+                vec!["use time::Date;\n".to_string()]
+            )
+        );
+
+        let ordered = RustGenerator(RustOptions {
+            rust_ordered_collections: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            rust(&ordered, "Map<string, Set<int>>"),
+            (
+                "BTreeMap<String, BTreeSet<i32>>".to_string(),
+                vec![
+                    "use std::collections::BTreeMap;\n".to_string(),
+                    "use std::collections::BTreeSet;\n".to_string()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let field = |value| {
+            RustGenerator::default().rust_field(
+                &udl,
+                &parse_type(value).unwrap(),
+                None,
+                &mut BTreeSet::new(),
+            )
+        };
+        let skip_none = "#[serde(default, skip_serializing_if = \"Option::is_none\")]".to_string();
+        assert_eq!(field("int32"), ("i32".to_string(), vec![]));
+        assert_eq!(field("int32?"), ("Option<i32>".to_string(), vec![]));
+        assert_eq!(
+            field("int32^"),
+            ("i32".to_string(), vec!["#[serde(default)]".to_string()])
+        );
+        assert_eq!(
+            field("int32?^"),
+            ("Option<i32>".to_string(), vec![skip_none.clone()])
+        );
+        assert_eq!(
+            field("string^"),
+            (
+                "String".to_string(),
+                vec!["#[serde(default, skip_serializing_if = \"String::is_empty\")]".to_string()]
+            )
+        );
+        assert_eq!(
+            field("bytes^"),
+            (
+                "Option<Vec<u8>>".to_string(),
+                vec![
+                    skip_none.clone(),
+                    "#[serde_as(as = \"Option<Base64>\")]".to_string()
+                ]
+            )
+        );
+        // Enums with a default variant implement `Default`
+        assert_eq!(
+            field("$enum::PaymentStatus^"),
+            (
+                "PaymentStatus".to_string(),
+                vec!["#[serde(default)]".to_string()]
+            )
+        );
+        assert_eq!(
+            field("$enum::SortBy^"),
+            ("Option<SortBy>".to_string(), vec![skip_none.clone()])
+        );
+        assert_eq!(
+            field("User^"),
+            ("Option<User>".to_string(), vec![skip_none.clone()])
+        );
+        assert_eq!(
+            field("List<User>^"),
+            (
+                "Vec<User>".to_string(),
+                vec!["#[serde(default, skip_serializing_if = \"Vec::is_empty\")]".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_serde_as() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let field = |value| {
+            let mut imports = BTreeSet::new();
+            let field = RustGenerator::default().rust_field(
+                &udl,
+                &parse_type(value).unwrap(),
+                None,
+                &mut imports,
+            );
+            (field, imports.into_iter().collect::<Vec<_>>())
+        };
+        assert_eq!(
+            field("bytes"),
+            (
+                (
+                    "Vec<u8>".to_string(),
+                    vec!["#[serde_as(as = \"Base64\")]".to_string()]
+                ),
+                vec!["use serde_with::base64::Base64;\n".to_string()]
+            )
+        );
+        assert_eq!(
+            field("Map<string, duration?>^").0,
+            (
+                "HashMap<String, Option<Duration>>".to_string(),
+                vec![
+                    "#[serde(default, skip_serializing_if = \"HashMap::is_empty\")]".to_string(),
+                    "#[serde_as(as = \"HashMap<_, Option<DurationMilliSeconds<u64>>>\")]"
+                        .to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            field("decimal?"),
+            (
+                ("Option<Decimal>".to_string(), vec![]),
+                vec!["use rust_decimal::Decimal;\n".to_string()]
+            )
+        );
+        assert_eq!(field("List<uuid>").0, ("Vec<Uuid>".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_enum_default() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let mut udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let generator = RustGenerator::default();
+        let find = |udl: &UDL, id: &str| udl.enums.iter().find(|e| e.id == id).unwrap().clone();
+
+        // `defaults:` entry
+        let (code, _) = generator.gen_enum(&udl, &find(&udl, "SortOrder"));
+        assert!(
+            code.ends_with("impl Default for SortOrder { fn default() -> Self { Self::KDesc } }")
+        );
+        // Enum level `default:`
+        let (code, _) = generator.gen_enum(&udl, &find(&udl, "PaymentStatus"));
+        assert!(
+            code.ends_with(
+                "impl Default for PaymentStatus { fn default() -> Self { Self::KDraft } }"
+            )
+        );
+        // `defaults:` prevails over the enum's own default
+        udl.defaults
+            .insert("PaymentStatus".to_string(), "sent".to_string());
+        let (code, _) = generator.gen_enum(&udl, &find(&udl, "PaymentStatus"));
+        assert!(
+            code.ends_with(
+                "impl Default for PaymentStatus { fn default() -> Self { Self::KSent } }"
+            )
+        );
+        // No default at all
+        let (code, _) = generator.gen_enum(&udl, &find(&udl, "SortBy"));
+        assert!(!code.contains("impl Default"));
+    }
+
+    #[test]
+    fn test_property_defaults() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+
+        let (code, _) = RustGenerator::default()
+            .gen_class(&udl, find("FetchInvoicesRequest"), None)
+            .unwrap();
+        for needle in [
+            "#[serde(default = \"FetchInvoicesRequest::default_limit\")]\n    pub limit: i8,",
+            "pub fn default_limit() -> i8 { 20 }",
+            "pub fn default_offset() -> i32 { 0 }",
+            "pub fn default_sort_by() -> SortBy { SortBy::KCreatedAt }",
+            "pub fn default_sort_order() -> SortOrder { SortOrder::KDesc }",
+            "impl Default for FetchInvoicesRequest { fn default() -> Self { Self { \
+             status: Default::default(), limit: Self::default_limit(), \
+             offset: Self::default_offset(), sort_by: Self::default_sort_by(), \
+             sort_order: Self::default_sort_order() } } }",
+        ] {
+            assert!(code.contains(needle), "missing {:?} in {}", needle, code);
+        }
+
+        // Validated models only get the default fns, to pass to `build()`
+        let (code, _) = RustGenerator::default()
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains("pub fn default_taxable() -> bool { true }"));
+        assert!(!code.contains("impl Default"));
+
+        // Type defaults apply like in the other generators
+        let (code, _) = RustGenerator::default()
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert!(code.contains(
+            "#[serde(default = \"LoginRequest::default_remember_me\")]\n     remember_me: bool,"
+        ));
+        assert!(code.contains("pub fn default_remember_me() -> bool { false }"));
+
+        let default = |value: &str, ty: &str| {
+            rust_default(&TypeDefault::Value(value.into()), &parse_type(ty).unwrap())
+        };
+        assert_eq!(default("it's", "string"), "\"it's\".to_string()");
+        assert_eq!(default("2", "float64"), "2.0");
+        assert_eq!(default("2.5", "float32?"), "Some(2.5)");
+        assert_eq!(default("null", "int?"), "None");
+    }
+
+    #[test]
+    fn test_serde_attrs() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Payment
+            variants:
+              - K_NONE
+              - id: K_CARD
+                value: "card"
+              - id: K_BANK
+                value:
+                  accountNumber: string
+                  paidAt: datetime?
+            "#,
+        )
+        .unwrap();
+        let generator = RustGenerator(RustOptions {
+            rust_rename_all: RenameRule::Camel,
+            ..Default::default()
+        });
+        let (code, _) = generator.gen_enum(&udl, &enumm);
+        assert_eq!(
+            code,
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n\
+             #[serde(tag = \"type\", rename_all_fields = \"camelCase\")]\n\
+             pub enum Payment {#[serde(rename = \"K_NONE\")]\n\
+             KNone,#[serde(rename = \"card\")]\n\
+             KCard,#[serde(rename = \"K_BANK\")]\n\
+             KBank {account_number: String,paid_at: Option<DateTime<Utc>>,},}"
+        );
+
+        let (code, _) = RustGenerator::default()
+            .gen_class(&udl, &udl.models[0], Some(&udl.enums[0]))
+            .unwrap();
+        assert!(
+            code.contains(
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n\
+                 #[serde(try_from = \"LoginRequestRaw\")]\n\
+                 #[serde(rename_all = \"snake_case\")]\n\
+                 pub struct LoginRequest {"
+            ),
+            "{}",
+            code
+        );
+        // Decoding goes through `build()`
+        assert!(code.contains(
+            "#[derive(Deserialize)]\n\
+             #[serde(rename_all = \"snake_case\")]\n\
+             struct LoginRequestRaw {"
+        ));
+        assert!(code.contains(
+            "fn try_from(raw: LoginRequestRaw) -> Result<Self, Self::Error> { \
+             Self::build(raw.email, raw.password, raw.remember_me) }"
+        ));
+        let (code, _) = RustGenerator::default().gen_enum(&udl, &udl.enums[0]);
+        assert!(code.contains(
+            "f.write_str(match self { Self::KInvalidEmail { .. } => \"Invalid email address\","
+        ));
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let error = |id: &str| udl.enums.iter().find(|e| e.id == id);
+        let generator = RustGenerator::default();
+
+        let (code, _) = generator
+            .gen_class(
+                &udl,
+                find("CreateInvoiceRequest"),
+                error("CreateInvoiceRequestError"),
+            )
+            .unwrap();
+        for expected in [
+            "    notes: Option<String>,",
+            "pub fn build(client_id: String, ",
+            ") -> Result<Self, CreateInvoiceRequestError> {",
+            "if notes.as_ref().is_some_and(|notes| notes.chars().count() > 500) \
+             { return Err(CreateInvoiceRequestError::KInvalidNotesMax); }",
+            "if tax_rate < 0 { return Err(CreateInvoiceRequestError::KInvalidTaxRateMin); }",
+            "pub fn notes(&self) -> Option<&str> { self.notes.as_deref() }",
+            "pub fn items(&self) -> &[InvoiceItem] { &self.items }",
+            "pub fn tax_rate(&self) -> i8 { self.tax_rate }",
+        ] {
+            assert!(
+                code.contains(expected),
+                "missing `{}` in {}",
+                expected,
+                code
+            );
+        }
+
+        let (code, imports) = generator
+            .gen_class(&udl, find("User"), error("UserNameError"))
+            .unwrap();
+        for expected in [
+            "static PHONE_FORMAT: LazyLock<Regex> = \
+             LazyLock::new(|| Regex::new(r\"^\\+?[0-9][0-9 ()-]{5,18}[0-9]$\").unwrap());",
+            "if phone.as_ref().is_some_and(|phone| !PHONE_FORMAT.is_match(phone)) \
+             { return Err(UserNameError::KInvalidPhone); }",
+        ] {
+            assert!(
+                code.contains(expected),
+                "missing `{}` in {}",
+                expected,
+                code
+            );
+        }
+        assert!(imports.contains("use regex::Regex;\n"));
+
+        // Models without validators keep public fields
+        let (code, _) = generator
+            .gen_class(&udl, find("InvoiceItem"), None)
+            .unwrap();
+        assert!(code.contains("    pub invoice_id: String,"));
+        assert!(!code.contains("impl InvoiceItem"));
+
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, _) = RustGenerator::default().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "/// Invoice management operations\n\
+             #[allow(async_fn_in_trait)]\n\
+             pub trait InvoiceService {\n\
+             \x20   async fn fetch_invoices(&self, fetch_invoices_request: FetchInvoicesRequest) -> Result<Invoice, InvoiceStatus>;\n\
+             \x20   /// Get a specific invoice by ID\n\
+             \x20   fn get_invoice(&self, id: String, include_items: Option<bool>) -> Option<Invoice>;\n\
+             \x20   async fn delete_invoice(&self, value: String) -> Result<(), ApiError>;\n\
+             \x20   fn ping(&self);\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_keywords() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Lesson
+            properties:
+              type: string
+              self: bool
+            "#,
+        )
+        .unwrap();
+        let generator = RustGenerator(RustOptions {
+            rust_rename_all: RenameRule::ScreamingSnake,
+            ..Default::default()
+        });
+        let (code, _) = generator.gen_class(&udl, &class, None).unwrap();
+        assert!(
+            code.contains(
+                "pub struct Lesson {    pub r#type: String,    \
+                 #[serde(rename = \"SELF\")]\n    \
+                 #[serde(default = \"Lesson::default_self\")]\n    pub self_: bool,}"
+            ),
+            "{}",
+            code
+        );
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: Lessons
+            methods:
+              - id: move
+                params:
+                  in: string
+            "#,
+        )
+        .unwrap();
+        let (code, _) = generator.gen_interface(&interface).unwrap();
+        assert!(
+            code.contains("    fn r#move(&self, r#in: String);\n"),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_time_serde() {
+        let source = std::fs::read_to_string("tests/generated_rust/collections.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let time = RustGenerator(RustOptions {
+            rust_datetime: DateTimeCrate::Time,
+            ..Default::default()
+        });
+        let code = time.generate(&udl).unwrap();
+        for expected in [
+            "time::serde::format_description!(iso_date, Date, \"[year]-[month]-[day]\");",
+            "#[serde(with = \"time::serde::rfc3339\")]\n    pub created_at: OffsetDateTime,",
+            "#[serde(with = \"time::serde::rfc3339::option\")]\n    pub archived_at: Option<OffsetDateTime>,",
+            "#[serde(with = \"iso_date::option\")]\n    pub due_on: Option<Date>,",
+        ] {
+            assert!(
+                code.contains(expected),
+                "missing `{}` in {}",
+                expected,
+                code
+            );
+        }
+        let code = RustGenerator::default().generate(&udl).unwrap();
+        assert!(!code.contains("#[serde(with"), "{}", code);
+    }
+
+    #[test]
+    fn test_hashed_derives() {
+        let source = std::fs::read_to_string("tests/generated_rust/collections.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let ordered = RustGenerator(RustOptions {
+            rust_ordered_collections: true,
+            ..Default::default()
+        });
+        for (generator, derives) in [
+            (RustGenerator::default(), "PartialEq, Eq, Hash, Serialize"),
+            (ordered, "PartialEq, Eq, Hash, PartialOrd, Ord, Serialize"),
+        ] {
+            assert!(generator.derives(&udl, "Tag").contains(derives));
+            assert!(generator.derives(&udl, "Shape").contains(derives));
+            assert!(generator.derives(&udl, "Label").contains(derives));
+            assert_eq!(
+                generator.derives(&udl, "Board"),
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n"
+            );
+        }
+    }
+
+    /// Builds and runs `tests/generated_rust` with the generated code, each module's
+    /// source passed in an environment variable.
+    fn build_generated(modules: &[(&str, &str, RustGenerator)]) {
+        let out_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/generated-rust");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let mut command = std::process::Command::new(env!("CARGO"));
+        command
+            .args(["run", "--quiet", "--locked", "--manifest-path"])
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/generated_rust/Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&out_dir);
+        for (var, manifest, generator) in modules {
+            let udl: UDL =
+                serde_yaml::from_str(&std::fs::read_to_string(manifest).unwrap()).unwrap();
+            let path = out_dir.join(format!("{}.rs", var.to_lowercase()));
+            std::fs::write(&path, generator.generate(&udl).unwrap()).unwrap();
+            command.env(var, path);
+        }
+        let output = command.output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_generated_code_round_trips() {
+        let ordered = RustGenerator(RustOptions {
+            rust_ordered_collections: true,
+            ..Default::default()
+        });
+        let time = RustGenerator(RustOptions {
+            rust_datetime: DateTimeCrate::Time,
+            ..Default::default()
+        });
+        build_generated(&[
+            (
+                "UDL_BILLING_APP",
+                "examples/billing_app.yaml",
+                RustGenerator::default(),
+            ),
+            (
+                "UDL_COLLECTIONS",
+                "tests/generated_rust/collections.yaml",
+                RustGenerator::default(),
+            ),
+            (
+                "UDL_ORDERED_COLLECTIONS",
+                "tests/generated_rust/collections.yaml",
+                ordered,
+            ),
+            (
+                "UDL_TIME_COLLECTIONS",
+                "tests/generated_rust/collections.yaml",
+                time,
+            ),
+        ]);
     }
 }
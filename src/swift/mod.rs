@@ -0,0 +1,1010 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use convert_case::ccase;
+use indexmap::IndexMap;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    core::TypeDefault,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::parse_type,
+};
+
+pub(crate) struct SwiftGenerator();
+
+/// Types whose `Codable` representation differs from the wire format of the Dart and
+/// Rust targets keep that format: decimals are strings, durations milliseconds and
+/// dates `yyyy-MM-dd` strings, as Foundation has no date-only type. `Date` fields
+/// need a matching `dateDecodingStrategy`.
+static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("bool", "Bool"),
+        ("int", "Int"),
+        ("float", "Double"),
+        ("string", "String"),
+        ("isize", "Int"),
+        ("int8", "Int8"),
+        ("int16", "Int16"),
+        ("int32", "Int32"),
+        ("int64", "Int64"),
+        ("usize", "UInt"),
+        ("uint8", "UInt8"),
+        ("uint16", "UInt16"),
+        ("uint32", "UInt32"),
+        ("uint64", "UInt64"),
+        ("float32", "Float"),
+        ("float64", "Double"),
+        ("datetime", "Date"),
+        ("date", "String"),
+        ("object", "JSONValue"),
+        ("uuid", "UUID"),
+        ("decimal", "String"),
+        ("bytes", "Data"),
+        ("url", "URL"),
+        ("duration", "Int"),
+        ("email", "String"),
+    ])
+});
+
+/// Declared once per file when an `object` field uses it.
+const JSON_VALUE_TYPE: &str = "enum JSONValue: Codable, Hashable {
+    case null
+    case bool(Bool)
+    case number(Double)
+    case string(String)
+    case array([JSONValue])
+    case object([String: JSONValue])
+
+    init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        if container.decodeNil() {
+            self = .null
+        } else if let value = try? container.decode(Bool.self) {
+            self = .bool(value)
+        } else if let value = try? container.decode(Double.self) {
+            self = .number(value)
+        } else if let value = try? container.decode(String.self) {
+            self = .string(value)
+        } else if let value = try? container.decode([JSONValue].self) {
+            self = .array(value)
+        } else {
+            self = .object(try container.decode([String: JSONValue].self))
+        }
+    }
+
+    func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        switch self {
+        case .null: try container.encodeNil()
+        case let .bool(value): try container.encode(value)
+        case let .number(value): try container.encode(value)
+        case let .string(value): try container.encode(value)
+        case let .array(value): try container.encode(value)
+        case let .object(value): try container.encode(value)
+        }
+    }
+}\n\n";
+
+/// Swift type without the optional marker.
+fn swift_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "Void".to_string(),
+        TypeKind::List(item) => format!("[{}]", swift_type(item)),
+        TypeKind::Set(item) => format!("Set<{}>", swift_type(item)),
+        TypeKind::Map(key, value) => format!("[{}: {}]", swift_type(key), swift_type(value)),
+        TypeKind::Generic { name, args } => format!(
+            "{}<{}>",
+            name,
+            args.iter().map(swift_type).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn swift_type(ty: &TypeExpr) -> String {
+    format!(
+        "{}{}",
+        swift_base_type(ty),
+        if ty.nullable { "?" } else { "" }
+    )
+}
+
+/// Escaped Swift string literal of `value`.
+fn swift_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Fields of a variant with associated values, with their Swift types.
+fn variant_fields(variant: &EnumKind) -> Vec<(&str, Option<TypeExpr>)> {
+    variant
+        .fields()
+        .into_iter()
+        .flatten()
+        .map(|(key, value)| (key.as_str(), parse_type(value)))
+        .collect()
+}
+
+/// Whether the model or enum `id` is the failure of a `Result`, which has to
+/// conform to `Error`: the `error:` of a model or the error of a service method.
+fn is_error_type(udl: &UDL, id: &str) -> bool {
+    udl.models
+        .iter()
+        .any(|class| class.error.as_deref() == Some(id))
+        || udl
+            .interfaces
+            .iter()
+            .flat_map(|interface| &interface.methods)
+            .any(|method| {
+                matches!(method.return_type(), Some(ReturnType::Result { err, .. }) if err.name() == id)
+            })
+}
+
+/// Swift literal of a resolved default.
+fn swift_default(default: &TypeDefault, ty: &TypeExpr) -> Option<String> {
+    match default {
+        TypeDefault::Variant(_, variant) => Some(format!(".{}", swift_name(variant.id()))),
+        TypeDefault::Value(value) if value == "null" && ty.nullable => Some("nil".to_string()),
+        TypeDefault::Value(value) if swift_base_type(ty) == "String" => Some(swift_string(value)),
+        TypeDefault::Value(value) => Some(value.to_string()),
+    }
+}
+
+/// Parameter list of a service method. A single param is passed without a label,
+/// inline params are labeled.
+fn method_params(params: &[(Option<&str>, TypeExpr)]) -> String {
+    params
+        .iter()
+        .map(|(name, ty)| {
+            format!(
+                "{}{}: {}",
+                if name.is_some() { "" } else { "_ " },
+                swift_name(param_name(*name, ty)),
+                swift_type(ty)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Condition under which `constraint` fails for the parameter `name`, unwrapped by
+/// `if let` when optional so a missing value passes. Limits compare numbers by value,
+/// strings by their count of unicode scalars, which are code points rather than
+/// grapheme clusters, and collections by their count. Patterns match the value as a
+/// string.
+fn constraint_failure(constraint: &Constraint, name: &str, ty: &TypeExpr) -> String {
+    let check = match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            if ty.is_number() {
+                format!("{} {} {}", name, operator, limit)
+            } else if swift_base_type(ty) == "String" {
+                format!("{}.unicodeScalars.count {} {}", name, operator, limit)
+            } else {
+                format!("{}.count {} {}", name, operator, limit)
+            }
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            let value = if swift_base_type(ty) == "String" {
+                name.to_string()
+            } else {
+                format!("String(describing: {})", name)
+            };
+            format!(
+                "{}.range(of: {}, options: .regularExpression) == nil",
+                value,
+                swift_string(constraint.check.pattern().unwrap_or_default())
+            )
+        }
+    };
+    if ty.nullable {
+        format!("let {}, {}", name, check)
+    } else {
+        check
+    }
+}
+
+/// Keywords, which need backticks to be used as names.
+const KEYWORDS: [&str; 49] = [
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "throw",
+    "switch",
+    "where",
+    "while",
+    "as",
+    "false",
+    "is",
+    "nil",
+    "self",
+    "super",
+    "true",
+];
+
+/// camelCase Swift name of a property, case, param or method, in backticks when it is
+/// a keyword. The raw value of a backticked coding key is the plain name.
+fn swift_name(name: &str) -> String {
+    let name = ccase!(camel, name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("`{}`", name)
+    } else {
+        name
+    }
+}
+
+/// `CodingKeys` mapping `names` to their snake_case wire names, or nothing when the
+/// names are the same.
+fn coding_keys<'a>(names: impl IntoIterator<Item = &'a str>, extra: &[&str]) -> String {
+    let keys = extra
+        .iter()
+        .map(|name| format!("        case {}\n", name))
+        .chain(names.into_iter().map(|name| {
+            if ccase!(camel, name) == ccase!(snake, name) {
+                format!("        case {}\n", swift_name(name))
+            } else {
+                format!(
+                    "        case {} = \"{}\"\n",
+                    swift_name(name),
+                    ccase!(snake, name)
+                )
+            }
+        }))
+        .collect::<String>();
+    if extra.is_empty() && !keys.contains(" = \"") {
+        return String::new();
+    }
+    format!(
+        "    private enum CodingKeys: String, CodingKey {{\n{}    }}\n",
+        keys
+    )
+}
+
+/// Expression decoding the field `name` from `container`. A missing key decodes to
+/// the default, an explicit null only when the field is not nullable.
+fn decode_field(name: &str, ty: &TypeExpr, default: Option<&str>, nullable: bool) -> String {
+    let key = swift_name(name);
+    // `try` covers the whole expression to its right
+    let present = format!(
+        "container.decodeIfPresent({}.self, forKey: .{})",
+        swift_base_type(ty),
+        key
+    );
+    match default {
+        Some(default) if default != "nil" && nullable => format!(
+            "try container.contains(.{}) ? {} : {}",
+            key, present, default
+        ),
+        Some(default) if default != "nil" => format!("try {} ?? {}", present, default),
+        _ if ty.nullable => format!("try {}", present),
+        _ => format!(
+            "try container.decode({}.self, forKey: .{})",
+            swift_type(ty),
+            key
+        ),
+    }
+}
+
+impl SwiftGenerator {
+    /// Enum with associated values, tagged by `type` on the wire like the Rust enum,
+    /// e.g. `{ "type": "K_PAID", "amount": 10 }`.
+    fn gen_tagged_enum(&self, enumm: &Enum, conformances: &str) -> String {
+        let mut code = format!("enum {}: {} {{\n", enumm.id, conformances);
+        let mut fields = IndexMap::new();
+        for variant in &enumm.variants {
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            let variant_fields = variant_fields(variant);
+            let params = variant_fields
+                .iter()
+                .map(|(key, ty)| {
+                    let ty = ty.as_ref().map_or("Any".to_string(), swift_type);
+                    format!("{}: {}", swift_name(key), ty)
+                })
+                .collect::<Vec<_>>();
+            code.push_str(&format!("    case {}", swift_name(variant.id())));
+            if !params.is_empty() {
+                code.push_str(&format!("({})", params.join(", ")));
+            }
+            code.push('\n');
+            fields.extend(variant_fields.into_iter().map(|(key, _)| (key, ())));
+        }
+        code.push('\n');
+        code.push_str(&coding_keys(fields.keys().copied(), &["type"]));
+
+        code.push_str(
+            "\n    init(from decoder: Decoder) throws {\n        \
+             let container = try decoder.container(keyedBy: CodingKeys.self)\n        \
+             switch try container.decode(String.self, forKey: .type) {\n",
+        );
+        for variant in &enumm.variants {
+            let args = variant_fields(variant)
+                .iter()
+                .map(|(key, ty)| {
+                    let (decode, ty) = match ty {
+                        Some(ty) if ty.nullable => ("decodeIfPresent", swift_base_type(ty)),
+                        ty => ("decode", ty.as_ref().map_or("Any".to_string(), swift_type)),
+                    };
+                    format!(
+                        "{}: try container.{}({}.self, forKey: .{})",
+                        swift_name(key),
+                        decode,
+                        ty,
+                        swift_name(key)
+                    )
+                })
+                .collect::<Vec<_>>();
+            code.push_str(&format!(
+                "        case {}: self = .{}{}\n",
+                swift_string(variant.wire_value()),
+                swift_name(variant.id()),
+                if args.is_empty() {
+                    String::new()
+                } else {
+                    format!("({})", args.join(", "))
+                }
+            ));
+        }
+        code.push_str(
+            "        case let type:\n            \
+             throw DecodingError.dataCorruptedError(\n                \
+             forKey: .type, in: container, debugDescription: \"Unknown type \\(type)\")\n        \
+             }\n    }\n",
+        );
+
+        code.push_str(
+            "\n    func encode(to encoder: Encoder) throws {\n        \
+             var container = encoder.container(keyedBy: CodingKeys.self)\n        \
+             switch self {\n",
+        );
+        for variant in &enumm.variants {
+            let fields = variant_fields(variant);
+            let bindings = fields
+                .iter()
+                .map(|(key, _)| swift_name(key))
+                .collect::<Vec<_>>();
+            if bindings.is_empty() {
+                code.push_str(&format!("        case .{}:\n", swift_name(variant.id())));
+            } else {
+                code.push_str(&format!(
+                    "        case let .{}({}):\n",
+                    swift_name(variant.id()),
+                    bindings.join(", ")
+                ));
+            }
+            code.push_str(&format!(
+                "            try container.encode({}, forKey: .type)\n",
+                swift_string(variant.wire_value())
+            ));
+            for (key, ty) in &fields {
+                let encode = match ty {
+                    Some(ty) if ty.nullable => "encodeIfPresent",
+                    _ => "encode",
+                };
+                code.push_str(&format!(
+                    "            try container.{}({}, forKey: .{})\n",
+                    encode,
+                    swift_name(key),
+                    swift_name(key)
+                ));
+            }
+        }
+        code.push_str("        }\n    }\n}\n");
+        code
+    }
+}
+
+impl LangGenerator for SwiftGenerator {
+    fn extension(&self) -> &str {
+        "swift"
+    }
+
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut code = String::new();
+        if let Some(desc) = enumm.description.as_ref() {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        let error = if is_error_type(udl, &enumm.id) {
+            ", Error"
+        } else {
+            ""
+        };
+        if enumm
+            .variants
+            .iter()
+            .any(|variant| !variant_fields(variant).is_empty())
+        {
+            let conformances = format!("Codable, Equatable, Hashable{}", error);
+            code.push_str(&self.gen_tagged_enum(enumm, &conformances));
+            return (code, BTreeSet::new());
+        }
+        // The raw value is the JSON value, like the Dart enum's `value`
+        code.push_str(&format!(
+            "enum {}: String, Codable, Hashable{} {{\n",
+            enumm.id, error
+        ));
+        for variant in &enumm.variants {
+            if let EnumKind::Complex(EnumVariant {
+                description: Some(desc),
+                ..
+            }) = variant
+            {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            code.push_str(&format!(
+                "    case {} = {}\n",
+                swift_name(variant.id()),
+                swift_string(variant.wire_value())
+            ));
+        }
+        code.push_str("}\n");
+        (code, BTreeSet::new())
+    }
+
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut code = String::new();
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        code.push_str(&format!(
+            "struct {}: Codable, Equatable, Hashable{} {{\n",
+            class.id,
+            if is_error_type(udl, &class.id) {
+                ", Error"
+            } else {
+                ""
+            }
+        ));
+        let mut fields = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            let default = udl
+                .property_default(prop, &ty)
+                .and_then(|default| swift_default(&default, &ty))
+                .or_else(|| ty.optional.then(|| "nil".to_string()));
+            let nullable = ty.nullable;
+            // Synthesized decoding only skips missing keys for optionals
+            let ty = TypeExpr {
+                nullable: ty.nullable || ty.optional,
+                ..ty
+            };
+            if let Some(desc) = prop.get(&PropertyKey::Description) {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            code.push_str(&format!(
+                "    let {}: {}\n",
+                swift_name(name),
+                swift_type(&ty)
+            ));
+            fields.push((name, ty, default, nullable));
+        }
+        let keys = coding_keys(fields.iter().map(|(name, ..)| name.as_str()), &[]);
+        if !keys.is_empty() {
+            code.push('\n');
+            code.push_str(&keys);
+        }
+
+        // Missing keys of fields with a default decode to it, which the synthesized
+        // decoding only does for nil
+        let has_defaults = fields
+            .iter()
+            .any(|(_, _, default, _)| default.as_deref().is_some_and(|default| default != "nil"));
+        if has_defaults && !is_validated(class) {
+            code.push_str(&format!(
+                "\n    init({}) {{\n",
+                fields
+                    .iter()
+                    .map(|(name, ty, default, _)| {
+                        let param = format!("{}: {}", swift_name(name), swift_type(ty));
+                        match default {
+                            Some(default) => format!("{} = {}", param, default),
+                            None => param,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            for (name, ..) in &fields {
+                code.push_str(&format!(
+                    "        self.{} = {}\n",
+                    swift_name(name),
+                    swift_name(name)
+                ));
+            }
+            code.push_str("    }\n");
+            code.push_str(
+                "\n    init(from decoder: Decoder) throws {\n        \
+                 let container = try decoder.container(keyedBy: CodingKeys.self)\n",
+            );
+            for (name, ty, default, nullable) in &fields {
+                code.push_str(&format!(
+                    "        self.{} = {}\n",
+                    swift_name(name),
+                    decode_field(name, ty, default.as_deref(), *nullable)
+                ));
+            }
+            code.push_str("    }\n");
+        }
+
+        if is_validated(class) {
+            let Some(error_enum) = error_enum else {
+                return Err(UdlError::MissingErrorEnum {
+                    model: class.id.clone(),
+                    error: class.error.clone().unwrap_or_default(),
+                });
+            };
+            let params = fields
+                .iter()
+                .map(|(name, ty, ..)| format!("{}: {}", swift_name(name), swift_type(ty)))
+                .collect::<Vec<_>>();
+            // Validated models are only built through `build()`
+            code.push_str(&format!("\n    private init({}) {{\n", params.join(", ")));
+            for (name, ..) in &fields {
+                code.push_str(&format!(
+                    "        self.{} = {}\n",
+                    swift_name(name),
+                    swift_name(name)
+                ));
+            }
+            code.push_str("    }\n");
+
+            // Decoding fails with the error of the first invalid field
+            code.push_str(
+                "\n    init(from decoder: Decoder) throws {\n        \
+                 let container = try decoder.container(keyedBy: CodingKeys.self)\n",
+            );
+            for (name, ty, default, nullable) in &fields {
+                code.push_str(&format!(
+                    "        let {} = {}\n",
+                    swift_name(name),
+                    decode_field(name, ty, default.as_deref(), *nullable)
+                ));
+            }
+            code.push_str(&format!(
+                "        switch Self.build({}) {{\n        \
+                 case let .success(value): self = value\n        \
+                 case let .failure(error): throw error\n        \
+                 }}\n    }}\n",
+                fields
+                    .iter()
+                    .map(|(name, ..)| format!("{}: {}", swift_name(name), swift_name(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+
+            code.push_str(&format!(
+                "\n    static func build({}) -> Result<{}, {}> {{\n",
+                fields
+                    .iter()
+                    .zip(params)
+                    .map(|((_, _, default, _), param)| match default {
+                        Some(default) => format!("{} = {}", param, default),
+                        None => param,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                class.id,
+                error_enum.id
+            ));
+            for constraint in constraints(class, error_enum)? {
+                let (_, ty, ..) = fields
+                    .iter()
+                    .find(|(name, ..)| name.as_str() == constraint.property)
+                    .unwrap();
+                code.push_str(&format!(
+                    "        if {} {{ return .failure(.{}) }}\n",
+                    constraint_failure(&constraint, &swift_name(constraint.property), ty),
+                    swift_name(constraint.variant)
+                ));
+            }
+            code.push_str(&format!(
+                "        return .success({}({}))\n    }}\n",
+                class.id,
+                fields
+                    .iter()
+                    .map(|(name, ..)| format!("{}: {}", swift_name(name), swift_name(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, BTreeSet::new()))
+    }
+
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("/// {}\n", desc));
+        }
+        code.push_str(&format!("protocol {} {{\n", interface.id));
+        for method in &interface.methods {
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("    /// {}\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => String::new(),
+                Some(ReturnType::Type(ty)) => format!(" -> {}", swift_type(&ty)),
+                Some(ReturnType::Result { ok, err }) => {
+                    let ok = ok.as_ref().map_or("Void".to_string(), swift_type);
+                    format!(" -> Result<{}, {}>", ok, swift_type(&err))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            code.push_str(&format!(
+                "    func {}({}){}{}\n",
+                swift_name(method.id.as_str()),
+                method_params(&params),
+                if method.is_async { " async" } else { "" },
+                returns
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, BTreeSet::new()))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut code = String::new();
+        for enum_def in &udl.enums {
+            code.push_str(&self.gen_enum(udl, enum_def).0);
+            code.push('\n');
+        }
+
+        for class in &udl.models {
+            let error_enum = udl
+                .enums
+                .iter()
+                .find(|e| e.id == class.clone().error.unwrap_or_default());
+            code.push_str(&self.gen_class(udl, class, error_enum)?.0);
+            code.push('\n');
+        }
+
+        for interface in &udl.interfaces {
+            code.push_str(&self.gen_interface(interface)?.0);
+            code.push('\n');
+        }
+
+        if code.contains("JSONValue") {
+            code.insert_str(0, JSON_VALUE_TYPE);
+        }
+        Ok(format!("import Foundation\n\n{}", code))
+    }
+
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "swift-format".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        let status = std::process::Command::new("swift-format")
+            .args(["format", "--in-place"])
+            .arg(path)
+            .stdout(Stdio::piped())
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swift_type() {
+        for (udl_type, expected) in [
+            ("int64", "Int64"),
+            ("uint8?", "UInt8?"),
+            ("datetime^", "Date"),
+            ("decimal", "String"),
+            ("List<$enum::SortOrder>", "[SortOrder]"),
+            ("Map<string, Set<int?>>?", "[String: Set<Int?>]?"),
+        ] {
+            assert_eq!(swift_type(&parse_type(udl_type).unwrap()), expected);
+        }
+        assert_eq!(swift_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+
+        // An explicit null of a nullable field stays null
+        let ty = parse_type("string?").unwrap();
+        assert_eq!(
+            decode_field("tag", &ty, Some("\"a\""), true),
+            "try container.contains(.tag) ? \
+             container.decodeIfPresent(String.self, forKey: .tag) : \"a\""
+        );
+        assert_eq!(
+            decode_field("tag", &ty, None, true),
+            "try container.decodeIfPresent(String.self, forKey: .tag)"
+        );
+    }
+
+    #[test]
+    fn test_gen_enum() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let (code, _) = SwiftGenerator().gen_enum(&udl, find("SortOrder"));
+        assert_eq!(
+            code,
+            "enum SortOrder: String, Codable, Hashable {\n    \
+             case kAsc = \"K_ASC\"\n    \
+             case kDesc = \"K_DESC\"\n}\n"
+        );
+        // Errors of models and service methods conform to `Error`
+        let (code, _) = SwiftGenerator().gen_enum(&udl, find("LoginError"));
+        assert!(code.starts_with("enum LoginError: String, Codable, Hashable, Error {\n"));
+        let (code, _) = SwiftGenerator().gen_enum(&udl, find("InvoiceStatus"));
+        assert!(code.contains("enum InvoiceStatus: String, Codable, Hashable, Error {\n"));
+
+        // Associated values have no synthesized `Codable` matching the `type` tag, the
+        // coding is written out and absent optionals are neither decoded nor encoded
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Discount
+            variants:
+              - id: K_FIXED
+                value:
+                  amountCents: int64
+                  default: bool?
+            "#,
+        )
+        .unwrap();
+        let (code, _) = SwiftGenerator().gen_enum(&udl, &enumm);
+        assert!(code.starts_with(
+            "enum Discount: Codable, Equatable, Hashable {\n    \
+             case kFixed(amountCents: Int64, `default`: Bool?)\n\n    \
+             private enum CodingKeys: String, CodingKey {\n        \
+             case type\n        \
+             case amountCents = \"amount_cents\"\n        \
+             case `default`\n    \
+             }\n"
+        ));
+        assert!(code.contains(
+            "        case \"K_FIXED\": self = .kFixed(amountCents: try container.decode(\
+             Int64.self, forKey: .amountCents), `default`: try container.decodeIfPresent(\
+             Bool.self, forKey: .`default`))\n"
+        ));
+        assert!(code.contains(
+            "        case let .kFixed(amountCents, `default`):\n            \
+             try container.encode(\"K_FIXED\", forKey: .type)\n            \
+             try container.encode(amountCents, forKey: .amountCents)\n            \
+             try container.encodeIfPresent(`default`, forKey: .`default`)\n"
+        ));
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let generator = SwiftGenerator();
+
+        let (code, _) = generator
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert!(code.contains("struct LoginRequest: Codable, Equatable, Hashable {\n"));
+        assert!(code.contains("        case rememberMe = \"remember_me\"\n"));
+        // Validated models are only built through `build()`
+        assert!(
+            code.contains(
+                "    private init(email: String, password: String, rememberMe: Bool) {\n"
+            )
+        );
+        assert!(code.contains(
+            "    static func build(email: String, password: String, rememberMe: Bool = false) \
+             -> Result<LoginRequest, LoginError> {\n"
+        ));
+        // Decoding goes through `build()` and throws its error
+        assert!(code.contains(
+            "        let rememberMe = try container.decodeIfPresent(Bool.self, \
+             forKey: .rememberMe) ?? false\n        \
+             switch Self.build(email: email, password: password, rememberMe: rememberMe) {\n        \
+             case let .success(value): self = value\n        \
+             case let .failure(error): throw error\n"
+        ));
+        assert!(code.contains(
+            "if email.range(of: \"^[^@\\\\s]+@[^@\\\\s]+\\\\.[^@\\\\s]+$\", \
+             options: .regularExpression) == nil { return .failure(.kInvalidEmail) }"
+        ));
+
+        // `count` counts grapheme clusters, `unicodeScalars` code points
+        let (code, _) = generator
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains("if let notes, notes.unicodeScalars.count > 500 { return"));
+        assert!(code.contains("if taxRate > 100 { return"));
+        assert!(code.contains("tag: String? = nil,"));
+
+        // Defaults apply to the initializer and to missing keys
+        let (code, _) = generator
+            .gen_class(&udl, find("FetchInvoicesRequest"), None)
+            .unwrap();
+        assert!(code.contains("    let sortOrder: SortOrder\n"));
+        assert!(code.contains(
+            "    init(status: InvoiceStatus?, limit: Int8 = 20, offset: Int = 0, \
+             sortBy: SortBy = .kCreatedAt, sortOrder: SortOrder = .kDesc) {\n"
+        ));
+        assert!(code.contains(
+            "        self.status = try container.decodeIfPresent(InvoiceStatus.self, \
+             forKey: .status)\n        \
+             self.limit = try container.decodeIfPresent(Int8.self, forKey: .limit) ?? 20\n"
+        ));
+        // Models without defaults keep the memberwise initializer
+        let (code, _) = generator.gen_class(&udl, find("ApiError"), None).unwrap();
+        assert!(!code.contains("init("));
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+
+        let code = generator.generate(&udl).unwrap();
+        assert!(code.starts_with("import Foundation\n\n"));
+        assert_eq!(code.matches("enum JSONValue:").count(), 1);
+    }
+
+    #[test]
+    fn test_keywords() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let error: Enum = serde_yaml::from_str(
+            r#"
+            id: CourseError
+            variants:
+              - id: K_INVALID_CLASS
+                value: "Class name is too long"
+                target: "limit:max"
+                target_field: class
+            "#,
+        )
+        .unwrap();
+        let class: Class = serde_yaml::from_str(
+            r#"
+            id: Course
+            error: CourseError
+            properties:
+              class:
+                type: string?
+                limit: ...10
+              is_default: bool
+            "#,
+        )
+        .unwrap();
+        let (code, _) = SwiftGenerator()
+            .gen_class(&udl, &class, Some(&error))
+            .unwrap();
+        // The raw value of a backticked key is the plain name
+        assert!(code.contains("    let `class`: String?\n"));
+        assert!(code.contains("        case `class`\n        case isDefault = \"is_default\"\n"));
+        assert!(code.contains("        self.`class` = `class`\n"));
+        assert!(code.contains("if let `class`, `class`.unicodeScalars.count > 10 {"));
+        assert!(code.contains("Course(`class`: `class`, isDefault: isDefault)"));
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, _) = SwiftGenerator().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "/// Invoice management operations\n\
+             protocol InvoiceService {\n\
+             \x20   func fetchInvoices(_ fetchInvoicesRequest: FetchInvoicesRequest) async -> Result<Invoice, InvoiceStatus>\n\
+             \x20   /// Get a specific invoice by ID\n\
+             \x20   func getInvoice(id: String, includeItems: Bool?) -> Invoice?\n\
+             \x20   func deleteInvoice(_ value: String) async -> Result<Void, ApiError>\n\
+             \x20   func ping()\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,540 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use convert_case::ccase;
+
+use crate::udl::{
+    LangGenerator, UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::{Check, Constraint, constraints, is_validated},
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, ReturnType, param_name},
+    types::{TypeExpr, TypeKind},
+    utils::parse_type,
+};
+
+pub(crate) struct TypeScriptGenerator();
+
+/// Models describe the JSON written by the Dart and Rust targets, so primitives map
+/// to their wire format: dates, decimals and bytes are strings, durations are
+/// milliseconds.
+static MAPPINGS: LazyLock<HashMap<&str, &str>> = std::sync::LazyLock::new(|| {
+    HashMap::from([
+        ("bool", "boolean"),
+        ("int", "number"),
+        ("float", "number"),
+        ("string", "string"),
+        ("isize", "number"),
+        ("int8", "number"),
+        ("int16", "number"),
+        ("int32", "number"),
+        ("int64", "number"),
+        ("usize", "number"),
+        ("uint8", "number"),
+        ("uint16", "number"),
+        ("uint32", "number"),
+        ("uint64", "number"),
+        ("float32", "number"),
+        ("float64", "number"),
+        ("datetime", "string"),
+        ("date", "string"),
+        ("object", "unknown"),
+        ("uuid", "string"),
+        ("decimal", "string"),
+        ("bytes", "string"),
+        ("url", "string"),
+        ("duration", "number"),
+        ("email", "string"),
+    ])
+});
+
+/// Declared once per file when a validated model or a service method uses it.
+const RESULT_TYPE: &str =
+    "export type Result<T, E> = { ok: true; value: T } | { ok: false; error: E };\n\n";
+
+/// TypeScript type without `| null`.
+fn ts_base_type(ty: &TypeExpr) -> String {
+    match &ty.kind {
+        TypeKind::Primitive(name) => MAPPINGS
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(name)
+            .to_string(),
+        TypeKind::Model(name) | TypeKind::Enum(name) => name.clone(),
+        TypeKind::Void => "void".to_string(),
+        // Sets are arrays on the wire
+        TypeKind::List(item) | TypeKind::Set(item) if item.nullable => {
+            format!("({})[]", ts_type(item))
+        }
+        TypeKind::List(item) | TypeKind::Set(item) => format!("{}[]", ts_type(item)),
+        // JSON object keys are always strings
+        TypeKind::Map(_, value) => format!("Record<string, {}>", ts_type(value)),
+        TypeKind::Generic { name, args } => format!(
+            "{}<{}>",
+            name,
+            args.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn ts_type(ty: &TypeExpr) -> String {
+    format!(
+        "{}{}",
+        ts_base_type(ty),
+        if ty.nullable { " | null" } else { "" }
+    )
+}
+
+/// Double quoted TypeScript string literal of `value`.
+fn ts_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Parameter list of a service method.
+fn method_params(params: &[(Option<&str>, TypeExpr)]) -> String {
+    params
+        .iter()
+        .map(|(name, ty)| {
+            let name = ccase!(camel, param_name(*name, ty));
+            format!("{}: {}", name, ts_type(ty))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Condition under which `constraint` fails for `expr` of type `ty`. Limits compare
+/// numbers by value, strings by their code points, which spreading iterates unlike
+/// the UTF-16 `length`, and anything else by its length. Patterns match the value as
+/// a string. A missing value passes.
+fn constraint_failure(constraint: &Constraint, expr: &str, ty: &TypeExpr) -> String {
+    let check = match constraint.check {
+        Check::Min(limit) | Check::Max(limit) => {
+            let operator = match constraint.check {
+                Check::Min(_) => "<",
+                _ => ">",
+            };
+            let value = match &ty.kind {
+                _ if ty.is_number() => expr.to_string(),
+                TypeKind::Map(..) => format!("Object.keys({}).length", expr),
+                _ if ts_base_type(ty) == "string" => format!("[...{}].length", expr),
+                _ => format!("{}.length", expr),
+            };
+            format!("{} {} {}", value, operator, limit)
+        }
+        Check::Format(_) | Check::Pattern(_) => {
+            let value = if ts_base_type(ty) == "string" {
+                expr.to_string()
+            } else {
+                format!("String({})", expr)
+            };
+            format!(
+                "!new RegExp({}).test({})",
+                ts_string(constraint.check.pattern().unwrap_or_default()),
+                value
+            )
+        }
+    };
+    if ty.nullable || ty.optional {
+        format!("{} != null && {}", expr, check)
+    } else {
+        check
+    }
+}
+
+impl LangGenerator for TypeScriptGenerator {
+    fn extension(&self) -> &str {
+        "ts"
+    }
+
+    fn gen_enum(&self, _udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>) {
+        let mut code = String::new();
+        if let Some(desc) = enumm.description.as_ref() {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        if enumm.is_tagged() {
+            // Same representation as the Rust enum, e.g.
+            // `{ type: "K_PAID", amount: 10 }`
+            code.push_str(&format!("export type {} =", enumm.id));
+            for variant in &enumm.variants {
+                if let EnumKind::Complex(EnumVariant {
+                    description: Some(desc),
+                    ..
+                }) = variant
+                {
+                    code.push_str(&format!("\n  /** {} */", desc));
+                }
+                code.push_str(&format!(
+                    "\n  | {{ type: {}",
+                    ts_string(variant.wire_value())
+                ));
+                if let Some(map) = variant.fields() {
+                    for (key, value) in map {
+                        let ty = parse_type(value).map_or(value.clone(), |ty| ts_type(&ty));
+                        code.push_str(&format!("; {}: {}", ccase!(snake, key), ty));
+                    }
+                }
+                code.push_str(" }");
+            }
+            code.push_str(";\n");
+        } else {
+            // A const object of the values and the union of their literal types share
+            // the enum's name
+            code.push_str(&format!("export const {} = {{\n", enumm.id));
+            for variant in &enumm.variants {
+                if let EnumKind::Complex(EnumVariant {
+                    description: Some(desc),
+                    ..
+                }) = variant
+                {
+                    code.push_str(&format!("  /** {} */\n", desc));
+                }
+                code.push_str(&format!(
+                    "  {}: {},\n",
+                    variant.id(),
+                    ts_string(variant.wire_value())
+                ));
+            }
+            code.push_str("} as const;\n\n");
+            code.push_str(&format!(
+                "export type {} = (typeof {})[keyof typeof {}];\n",
+                enumm.id, enumm.id, enumm.id
+            ));
+        }
+        (code, BTreeSet::new())
+    }
+
+    fn gen_class(
+        &self,
+        _udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut code = String::new();
+        if let Some(desc) = &class.description {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        code.push_str(&format!("export interface {} {{\n", class.id));
+        let readonly = if class.immutable == Some(true) {
+            "readonly "
+        } else {
+            ""
+        };
+        let mut field_types = vec![];
+        for (name, prop) in &class.properties {
+            let type_name = prop.type_name().ok_or_else(|| UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            })?;
+            let ty = parse_type(type_name).ok_or_else(|| UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            })?;
+            if let Some(desc) = prop.get(&PropertyKey::Description) {
+                code.push_str(&format!("  /** {} */\n", desc));
+            }
+            // Keys are the snake_case wire names
+            code.push_str(&format!(
+                "  {}{}{}: {};\n",
+                readonly,
+                ccase!(snake, name),
+                if ty.optional { "?" } else { "" },
+                ts_type(&ty)
+            ));
+            field_types.push((name, ty));
+        }
+        code.push_str("}\n");
+
+        if is_validated(class) {
+            let Some(error_enum) = error_enum else {
+                return Err(UdlError::MissingErrorEnum {
+                    model: class.id.clone(),
+                    error: class.error.clone().unwrap_or_default(),
+                });
+            };
+            code.push_str(&format!(
+                "\nexport function build{}(value: {}): Result<{}, {}> {{\n",
+                class.id, class.id, class.id, error_enum.id
+            ));
+            for constraint in constraints(class, error_enum)? {
+                let (_, ty) = field_types
+                    .iter()
+                    .find(|(name, _)| name.as_str() == constraint.property)
+                    .unwrap();
+                let error = if error_enum.is_tagged() {
+                    let variant = error_enum.find_variant(constraint.variant).unwrap();
+                    format!("{{ type: {} }}", ts_string(variant.wire_value()))
+                } else {
+                    format!("{}.{}", error_enum.id, constraint.variant)
+                };
+                code.push_str(&format!(
+                    "  if ({}) {{\n    return {{ ok: false, error: {} }};\n  }}\n",
+                    constraint_failure(
+                        &constraint,
+                        &format!("value.{}", ccase!(snake, constraint.property)),
+                        ty
+                    ),
+                    error
+                ));
+            }
+            code.push_str("  return { ok: true, value };\n}\n");
+        }
+        Ok((code, BTreeSet::new()))
+    }
+
+    fn gen_interface(&self, interface: &Interface) -> Result<(String, BTreeSet<String>), UdlError> {
+        let mut code = String::new();
+        if let Some(desc) = &interface.description {
+            code.push_str(&format!("/** {} */\n", desc));
+        }
+        code.push_str(&format!("export interface {} {{\n", interface.id));
+        for method in &interface.methods {
+            if let Some(desc) = &method.description {
+                code.push_str(&format!("  /** {} */\n", desc));
+            }
+            let params = method
+                .param_types()
+                .map_err(|value| UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: value.to_string(),
+                })?;
+            let returns = match method.return_type() {
+                Some(ReturnType::Void) => "void".to_string(),
+                Some(ReturnType::Type(ty)) => ts_type(&ty),
+                Some(ReturnType::Result { ok, err }) => {
+                    let ok = ok.as_ref().map_or("void".to_string(), ts_type);
+                    format!("Result<{}, {}>", ok, ts_type(&err))
+                }
+                None => {
+                    return Err(UdlError::InvalidReturnType {
+                        interface: interface.id.clone(),
+                        method: method.id.clone(),
+                        value: method.returns.clone().unwrap_or_default(),
+                    });
+                }
+            };
+            let returns = if method.is_async {
+                format!("Promise<{}>", returns)
+            } else {
+                returns
+            };
+            code.push_str(&format!(
+                "  {}({}): {};\n",
+                ccase!(camel, method.id.as_str()),
+                method_params(&params),
+                returns
+            ));
+        }
+        code.push_str("}\n");
+        Ok((code, BTreeSet::new()))
+    }
+
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError> {
+        let mut code = String::new();
+        for enum_def in &udl.enums {
+            code.push_str(&self.gen_enum(udl, enum_def).0);
+            code.push('\n');
+        }
+
+        for class in &udl.models {
+            let error_enum = udl
+                .enums
+                .iter()
+                .find(|e| e.id == class.clone().error.unwrap_or_default());
+            code.push_str(&self.gen_class(udl, class, error_enum)?.0);
+            code.push('\n');
+        }
+
+        for interface in &udl.interfaces {
+            code.push_str(&self.gen_interface(interface)?.0);
+            code.push('\n');
+        }
+
+        if code.contains("Result<") {
+            code.insert_str(0, RESULT_TYPE);
+        }
+        Ok(code)
+    }
+
+    fn format(&self, path: &str) -> Result<(), UdlError> {
+        let format_error = |source| UdlError::Format {
+            tool: "prettier".to_string(),
+            path: path.to_string(),
+            source,
+        };
+        let status = std::process::Command::new("prettier")
+            .arg("--write")
+            .arg(path)
+            .stdout(Stdio::piped())
+            .status()
+            .map_err(format_error)?;
+        if !status.success() {
+            return Err(format_error(std::io::Error::other(status.to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ts_type() {
+        for (udl_type, expected) in [
+            ("int64", "number"),
+            ("string?", "string | null"),
+            ("datetime^", "string"),
+            ("bytes", "string"),
+            ("object?", "unknown | null"),
+            ("List<$enum::SortOrder>", "SortOrder[]"),
+            ("Set<int?>", "(number | null)[]"),
+            (
+                "Map<int, List<InvoiceItem>>?",
+                "Record<string, InvoiceItem[]> | null",
+            ),
+            ("Result<User, ApiError>", "Result<User, ApiError>"),
+        ] {
+            assert_eq!(ts_type(&parse_type(udl_type).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn test_gen_enum() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let (code, _) = TypeScriptGenerator().gen_enum(&udl, find("PaymentStatus"));
+        assert_eq!(
+            code,
+            "/** Status of an invoice */\n\
+             export const PaymentStatus = {\n\
+             \x20 /** Invoice not yet sent */\n\
+             \x20 K_DRAFT: \"draft\",\n\
+             \x20 K_SENT: \"sent\",\n\
+             \x20 K_OVERDUE: \"overdue\",\n\
+             \x20 K_CANCELLED: \"cancelled\",\n\
+             \x20 K_PAID: \"paid\",\n\
+             } as const;\n\n\
+             export type PaymentStatus = (typeof PaymentStatus)[keyof typeof PaymentStatus];\n"
+        );
+
+        // The union mirrors the JSON: the tag is the wire value and fields keep their
+        // wire names, 64-bit numbers included
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: Delivery
+            variants:
+              - id: K_PICKUP
+                value: "pick-up \"store\""
+              - id: K_COURIER
+                value:
+                  trackingUrl: url
+                  parcelIds: Set<int64>
+            "#,
+        )
+        .unwrap();
+        let (code, _) = TypeScriptGenerator().gen_enum(&udl, &enumm);
+        assert_eq!(
+            code,
+            "export type Delivery =\n\
+             \x20 | { type: \"pick-up \\\"store\\\"\" }\n\
+             \x20 | { type: \"K_COURIER\"; tracking_url: string; parcel_ids: number[] };\n"
+        );
+    }
+
+    #[test]
+    fn test_build() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: UDL = serde_yaml::from_str(&source).unwrap();
+        let find = |id: &str| udl.models.iter().find(|c| c.id == id).unwrap();
+        let generator = TypeScriptGenerator();
+
+        let (code, _) = generator
+            .gen_class(&udl, find("LoginRequest"), Some(&udl.enums[0]))
+            .unwrap();
+        assert!(code.starts_with(
+            "/** User login request */\n\
+             export interface LoginRequest {\n\
+             \x20 /** User email address */\n\
+             \x20 email: string;\n\
+             \x20 password: string;\n\
+             \x20 remember_me: boolean;\n\
+             }\n\n\
+             export function buildLoginRequest(value: LoginRequest): Result<LoginRequest, LoginError> {\n\
+             \x20 if (!new RegExp(\"^[^@\\\\s]+@[^@\\\\s]+\\\\.[^@\\\\s]+$\").test(value.email)) {\n\
+             \x20   return { ok: false, error: LoginError.K_INVALID_EMAIL };\n\
+             \x20 }\n\
+             \x20 if ([...value.password].length < 8) {\n"
+        ));
+        assert!(code.ends_with("  return { ok: true, value };\n}\n"));
+
+        // `length` counts UTF-16 units, spreading the string counts code points
+        let (code, _) = generator
+            .gen_class(&udl, find("CreateInvoiceRequest"), Some(&udl.enums[2]))
+            .unwrap();
+        assert!(code.contains("if (value.notes != null && [...value.notes].length > 500) {"));
+        assert!(code.contains("if (value.tax_rate > 100) {"));
+
+        // No validators, no factory
+        let (code, _) = generator.gen_class(&udl, find("Invoice"), None).unwrap();
+        assert!(!code.contains("function"));
+        assert!(matches!(
+            generator.gen_class(&udl, find("LoginRequest"), None),
+            Err(UdlError::MissingErrorEnum { .. })
+        ));
+
+        let code = generator.generate(&udl).unwrap();
+        assert!(code.starts_with(RESULT_TYPE));
+    }
+
+    #[test]
+    fn test_gen_interface() {
+        let interface: Interface = serde_yaml::from_str(
+            r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            methods:
+              - id: fetchInvoices
+                params: FetchInvoicesRequest
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+              - id: get_invoice
+                description: "Get a specific invoice by ID"
+                params:
+                  id: string
+                  includeItems: bool?
+                returns: Invoice?
+              - id: delete_invoice
+                params: string
+                returns: Result<void, ApiError>
+                async: true
+              - id: ping
+            "#,
+        )
+        .unwrap();
+        let (code, _) = TypeScriptGenerator().gen_interface(&interface).unwrap();
+        assert_eq!(
+            code,
+            "/** Invoice management operations */\n\
+             export interface InvoiceService {\n\
+             \x20 fetchInvoices(fetchInvoicesRequest: FetchInvoicesRequest): Promise<Result<Invoice, InvoiceStatus>>;\n\
+             \x20 /** Get a specific invoice by ID */\n\
+             \x20 getInvoice(id: string, includeItems: boolean | null): Invoice | null;\n\
+             \x20 deleteInvoice(value: string): Promise<Result<void, ApiError>>;\n\
+             \x20 ping(): void;\n\
+             }\n"
+        );
+    }
+}
@@ -1,21 +1,23 @@
-use std::collections::HashMap;
-
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Class {
     pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub immutable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
-    pub properties: HashMap<String, Property>,
+    pub properties: IndexMap<String, Property>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Property {
     Type(String),
-    Map(HashMap<PropertyKey, String>),
+    Map(IndexMap<PropertyKey, String>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -29,6 +31,24 @@ pub enum PropertyKey {
     Max,
     Private,
     Default,
+    Pattern,
+}
+
+impl Property {
+    /// UDL type string, for both the short and the map syntax.
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            Property::Type(ty) => Some(ty),
+            Property::Map(map) => map.get(&PropertyKey::Type).map(|ty| ty.as_str()),
+        }
+    }
+
+    pub fn get(&self, key: &PropertyKey) -> Option<&str> {
+        match self {
+            Property::Type(_) => None,
+            Property::Map(map) => map.get(key).map(|value| value.as_str()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -62,32 +82,32 @@ mod tests {
         let class: Class = serde_yaml::from_str(test_1).unwrap();
         assert_eq!(class.id, "User");
         assert_eq!(class.description.unwrap(), "User profile data");
-        assert_eq!(class.immutable.unwrap(), true);
+        assert!(class.immutable.unwrap());
         assert_eq!(class.error, None);
         assert_eq!(
             class.properties.get("id").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string".to_string()),
                 (PropertyKey::Format, "uuid".to_string()),
             ]))
         );
         assert_eq!(
             class.properties.get("email").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string".to_string()),
                 (PropertyKey::Format, "email".to_string()),
             ]))
         );
         assert_eq!(
             class.properties.get("name").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string".to_string()),
                 (PropertyKey::Limit, "1...100".to_string()),
             ]))
         );
         assert_eq!(
             class.properties.get("phone").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string?".to_string()),
                 (PropertyKey::Format, "phone".to_string()),
             ]))
@@ -132,7 +152,7 @@ mod tests {
         assert_eq!(class.properties.len(), 3);
         assert_eq!(
             class.properties.get("email").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string".to_string()),
                 (PropertyKey::Format, "email".to_string()),
                 (PropertyKey::Description, "User email address".to_string()),
@@ -140,7 +160,7 @@ mod tests {
         );
         assert_eq!(
             class.properties.get("password").unwrap(),
-            &Property::Map(HashMap::from([
+            &Property::Map(IndexMap::from([
                 (PropertyKey::Type, "string".to_string()),
                 (PropertyKey::Limit, "8...32".to_string()),
             ]))
@@ -149,5 +169,9 @@ mod tests {
             class.properties.get("remember_me").unwrap(),
             &Property::Type("bool".to_string())
         );
+        assert_eq!(
+            class.properties.keys().collect::<Vec<_>>(),
+            vec!["email", "password", "remember_me"]
+        );
     }
 }
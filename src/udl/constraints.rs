@@ -0,0 +1,239 @@
+use crate::udl::{
+    UdlError,
+    class::{Class, Property, PropertyKey},
+    enums::Enum,
+    utils::{extract_enum_variant, parse_limit_validator},
+};
+
+/// Property keys that make a model with an `error:` enum validated.
+pub const VALIDATORS: [PropertyKey; 6] = [
+    PropertyKey::Limit,
+    PropertyKey::Format,
+    PropertyKey::Pattern,
+    PropertyKey::Default,
+    PropertyKey::Min,
+    PropertyKey::Max,
+];
+
+/// Built-in values of `format:`. The patterns are understood by both Dart's `RegExp`
+/// and the `regex` crate, so every target accepts the same values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Email,
+    Uuid,
+    Phone,
+    Url,
+    Ipv4,
+    Ipv6,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Option<Format> {
+        match value {
+            "email" => Some(Format::Email),
+            "uuid" => Some(Format::Uuid),
+            "phone" => Some(Format::Phone),
+            "url" => Some(Format::Url),
+            "ipv4" => Some(Format::Ipv4),
+            "ipv6" => Some(Format::Ipv6),
+            _ => None,
+        }
+    }
+
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Format::Email => r"^[^@\s]+@[^@\s]+\.[^@\s]+$",
+            Format::Uuid => {
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+            }
+            Format::Phone => r"^\+?[0-9][0-9 ()-]{5,18}[0-9]$",
+            Format::Url => r"^[a-zA-Z][a-zA-Z0-9+.-]*://[^\s/?#]+[^\s]*$",
+            Format::Ipv4 => {
+                r"^((25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}(25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])$"
+            }
+            Format::Ipv6 => {
+                r"^(([0-9a-fA-F]{1,4}:){7}[0-9a-fA-F]{1,4}|(([0-9a-fA-F]{1,4}:){0,6}[0-9a-fA-F]{1,4})?::(([0-9a-fA-F]{1,4}:){0,6}[0-9a-fA-F]{1,4})?)$"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check<'a> {
+    /// Fails when the value is below the limit, reported by a `limit:min` variant
+    Min(isize),
+    /// Fails when the value is above the limit, reported by a `limit:max` variant
+    Max(isize),
+    /// Fails when the value does not match, reported by a `format:<name>` variant
+    Format(Format),
+    /// Fails when the value does not match the `pattern:` regex, reported by a
+    /// `pattern` variant. Patterns use the syntax of the `regex` crate, without
+    /// look-around or backreferences, so every target's engine accepts them
+    Pattern(&'a str),
+}
+
+impl Check<'_> {
+    fn target(&self) -> String {
+        match self {
+            Check::Min(_) => "limit:min".to_string(),
+            Check::Max(_) => "limit:max".to_string(),
+            Check::Format(format) => format!("format:{}", format!("{:?}", format).to_lowercase()),
+            Check::Pattern(_) => "pattern".to_string(),
+        }
+    }
+
+    /// Regex the value has to match, for format and pattern checks.
+    pub fn pattern(&self) -> Option<&str> {
+        match self {
+            Check::Format(format) => Some(format.pattern()),
+            Check::Pattern(pattern) => Some(pattern),
+            Check::Min(_) | Check::Max(_) => None,
+        }
+    }
+}
+
+/// A check of a property. Limits apply to the value of numbers and to the length of
+/// everything else, patterns to the value as a string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint<'a> {
+    /// Property name as written in the manifest
+    pub property: &'a str,
+    pub check: Check<'a>,
+    /// Error variant returned when the check fails
+    pub variant: &'a str,
+}
+
+/// Checks enforced by the validated constructor of `class`, in property order. A check
+/// is only enforced when `error_enum` has a variant targeting it: the only variant
+/// with that target, or else the one whose `target_field` is the property.
+pub fn constraints<'a>(
+    class: &'a Class,
+    error_enum: &'a Enum,
+) -> Result<Vec<Constraint<'a>>, UdlError> {
+    let mut constraints = vec![];
+    for (name, prop) in &class.properties {
+        let Property::Map(map) = prop else {
+            continue;
+        };
+        for (key, value) in map {
+            let invalid_number = || UdlError::InvalidNumber {
+                model: class.id.clone(),
+                property: name.clone(),
+                key: format!("{:?}", key).to_lowercase(),
+                value: value.clone(),
+            };
+            let (min, max) = match key {
+                PropertyKey::Limit => {
+                    let (min, max, _) =
+                        parse_limit_validator(value).ok_or_else(|| UdlError::InvalidLimit {
+                            model: class.id.clone(),
+                            property: name.clone(),
+                            value: value.clone(),
+                        })?;
+                    (min, max)
+                }
+                PropertyKey::Min => (value.parse().map_err(|_| invalid_number())?, -1),
+                PropertyKey::Max => (-1, value.parse().map_err(|_| invalid_number())?),
+                _ => (-1, -1),
+            };
+            let checks = match key {
+                PropertyKey::Format => {
+                    let format = Format::parse(value).ok_or_else(|| UdlError::UnknownFormat {
+                        model: class.id.clone(),
+                        property: name.clone(),
+                        value: value.clone(),
+                    })?;
+                    vec![Check::Format(format)]
+                }
+                PropertyKey::Pattern => vec![Check::Pattern(value)],
+                _ => [(min, Check::Min(min)), (max, Check::Max(max))]
+                    .into_iter()
+                    .filter(|(limit, _)| *limit != -1)
+                    .map(|(_, check)| check)
+                    .collect(),
+            };
+            for check in checks {
+                let variants = extract_enum_variant(error_enum, &check.target());
+                let variant = match variants[..] {
+                    [(id, _, _)] => Some(id),
+                    _ => variants
+                        .iter()
+                        .find(|(_, _, field)| field.as_ref() == Some(name))
+                        .map(|(id, _, _)| *id),
+                };
+                if let Some(variant) = variant {
+                    constraints.push(Constraint {
+                        property: name,
+                        check,
+                        variant,
+                    });
+                }
+            }
+        }
+    }
+    Ok(constraints)
+}
+
+/// Whether `class` gets a validated constructor, which needs an `error:` enum and at
+/// least one validator.
+pub fn is_validated(class: &Class) -> bool {
+    class.error.is_some()
+        && class.properties.values().any(|prop| match prop {
+            Property::Map(map) => map.keys().any(|key| VALIDATORS.contains(key)),
+            Property::Type(_) => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints() {
+        let source = std::fs::read_to_string("examples/billing_app.yaml").unwrap();
+        let udl: crate::udl::UDL = serde_yaml::from_str(&source).unwrap();
+        let find_enum = |id: &str| udl.enums.iter().find(|e| e.id == id).unwrap();
+
+        let constraints = constraints(&udl.models[0], find_enum("LoginError")).unwrap();
+        assert_eq!(
+            constraints,
+            vec![
+                Constraint {
+                    property: "email",
+                    check: Check::Format(Format::Email),
+                    variant: "K_INVALID_EMAIL",
+                },
+                Constraint {
+                    property: "password",
+                    check: Check::Min(8),
+                    variant: "K_INVALID_PASSWORD_MIN",
+                },
+                Constraint {
+                    property: "password",
+                    check: Check::Max(32),
+                    variant: "K_INVALID_PASSWORD_MAX",
+                },
+            ]
+        );
+
+        // `...500` has no lower bound, `0..0..100` checks both
+        let class = udl
+            .models
+            .iter()
+            .find(|c| c.id == "CreateInvoiceRequest")
+            .unwrap();
+        let constraints = super::constraints(class, find_enum("CreateInvoiceRequestError"))
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.property, c.check))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                ("notes", Check::Max(500)),
+                ("tax_rate", Check::Min(0)),
+                ("tax_rate", Check::Max(100)),
+            ]
+        );
+    }
+}
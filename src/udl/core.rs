@@ -1,15 +1,189 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-use crate::udl::{class::Class, enums::Enum};
+use crate::udl::{
+    class::{Class, Property, PropertyKey},
+    enums::{Enum, EnumKind, EnumVariant, EnumVariantValue},
+    interface::Interface,
+    types::{TypeExpr, TypeKind},
+    utils::{parse_limit_validator, parse_type},
+};
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct UDL {
     #[serde(rename = "udl_version")]
     pub version: String,
     pub project: ProjectMeta,
+    /// Default values by type name, e.g. `bool: false` or `InvoiceStatus: SENT`
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub defaults: IndexMap<String, String>,
     pub enums: Vec<Enum>,
     pub models: Vec<Class>,
+    #[serde(default, rename = "interface", skip_serializing_if = "Vec::is_empty")]
+    pub interfaces: Vec<Interface>,
 }
+impl UDL {
+    /// Default variant of `enumm`. A `defaults:` entry prevails over the enum's own
+    /// `default:`.
+    pub fn enum_default<'a>(&'a self, enumm: &'a Enum) -> Option<&'a EnumKind> {
+        self.defaults
+            .get(&enumm.id)
+            .or(enumm.default.as_ref())
+            .and_then(|name| enumm.find_variant(name))
+    }
+
+    /// Default value of a property type: the default variant for `$enum::` types,
+    /// otherwise the `defaults:` entry for the type.
+    pub fn type_default(&self, ty: &TypeExpr) -> Option<TypeDefault<'_>> {
+        match &ty.kind {
+            TypeKind::Enum(enum_id) => {
+                let enumm = self.enums.iter().find(|e| &e.id == enum_id)?;
+                self.enum_default(enumm)
+                    .map(|variant| TypeDefault::Variant(enumm, variant))
+            }
+            TypeKind::Primitive(name) => self
+                .defaults
+                .get(name)
+                .map(|value| TypeDefault::Value(Cow::Borrowed(value))),
+            _ => None,
+        }
+    }
+
+    /// Default value of a property of type `ty`: its own `default:`, else the middle
+    /// value of a numeric `limit:` such as `1..20..100` or `0..`, else the
+    /// [type default](UDL::type_default). Nullable properties without one of their own
+    /// default to null.
+    pub fn property_default<'a>(
+        &'a self,
+        prop: &'a Property,
+        ty: &TypeExpr,
+    ) -> Option<TypeDefault<'a>> {
+        if let Some(value) = prop.get(&PropertyKey::Default) {
+            if let TypeKind::Enum(enum_id) = &ty.kind
+                && let Some(enumm) = self.enums.iter().find(|e| &e.id == enum_id)
+                && let Some(variant) = enumm.find_variant(value)
+            {
+                return Some(TypeDefault::Variant(enumm, variant));
+            }
+            return Some(TypeDefault::Value(Cow::Borrowed(value)));
+        }
+        if ty.is_number()
+            && let Some((_, _, default)) = prop
+                .get(&PropertyKey::Limit)
+                .and_then(parse_limit_validator)
+            && default != -1
+        {
+            return Some(TypeDefault::Value(Cow::Owned(default.to_string())));
+        }
+        if ty.nullable {
+            return None;
+        }
+        self.type_default(ty)
+    }
+
+    /// Ids of the models and enums used as set elements or map keys, directly or
+    /// through the fields of one. Their generated types need equality and hashing.
+    pub fn hashed_types(&self) -> BTreeSet<&str> {
+        let mut keys = Vec::new();
+        let ids = self
+            .models
+            .iter()
+            .map(|m| &m.id)
+            .chain(self.enums.iter().map(|e| &e.id));
+        for (_, fields) in ids.filter_map(|id| self.field_types(id)) {
+            for field in &fields {
+                field.visit(&mut |ty| match &ty.kind {
+                    TypeKind::Set(item) => keys.push(item.as_ref().clone()),
+                    TypeKind::Map(key, _) => keys.push(key.as_ref().clone()),
+                    _ => {}
+                });
+            }
+        }
+        let mut hashed = BTreeSet::new();
+        for key in &keys {
+            self.visit_reachable(key, &mut hashed, &mut |_| {});
+        }
+        hashed
+    }
+
+    /// First type reachable from the set element or map key type `ty` that cannot be
+    /// compared and hashed: floats, `object`, sets, maps and other types with
+    /// arguments.
+    pub fn unhashable_type(&self, ty: &TypeExpr) -> Option<String> {
+        let mut unhashable = None;
+        self.visit_reachable(ty, &mut BTreeSet::new(), &mut |ty| {
+            let hashable = match &ty.kind {
+                TypeKind::Primitive(name) => !(name.starts_with("float") || name == "object"),
+                TypeKind::Set(_) | TypeKind::Map(..) | TypeKind::Generic { .. } => false,
+                _ => true,
+            };
+            if !hashable && unhashable.is_none() {
+                unhashable = Some(ty.to_string());
+            }
+        });
+        unhashable
+    }
+
+    /// Calls `f` on `ty`, its type arguments and the field types of the models and
+    /// enums it references. Each model and enum is entered once, tracked in `seen`.
+    fn visit_reachable<'a>(
+        &'a self,
+        ty: &TypeExpr,
+        seen: &mut BTreeSet<&'a str>,
+        f: &mut dyn FnMut(&TypeExpr),
+    ) {
+        ty.visit(&mut |ty| {
+            f(ty);
+            if let TypeKind::Model(id) | TypeKind::Enum(id) = &ty.kind
+                && let Some((id, fields)) = self.field_types(id)
+                && seen.insert(id)
+            {
+                for field in &fields {
+                    self.visit_reachable(field, seen, f);
+                }
+            }
+        });
+    }
+
+    /// Id and field types of the model or enum `id`, the payload fields for enums.
+    /// Malformed types are skipped.
+    fn field_types(&self, id: &str) -> Option<(&str, Vec<TypeExpr>)> {
+        if let Some(class) = self.models.iter().find(|m| m.id == id) {
+            let fields = class
+                .properties
+                .values()
+                .filter_map(|prop| prop.type_name().and_then(parse_type))
+                .collect();
+            return Some((&class.id, fields));
+        }
+        let enumm = self.enums.iter().find(|e| e.id == id)?;
+        let fields = enumm
+            .variants
+            .iter()
+            .filter_map(|variant| match variant {
+                EnumKind::Complex(EnumVariant {
+                    value: EnumVariantValue::Multiple(map),
+                    ..
+                }) => Some(map.values()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|ty| parse_type(ty))
+            .collect();
+        Some((&enumm.id, fields))
+    }
+}
+
+/// A resolved default value, see [`UDL::property_default`].
+#[derive(Debug, Clone)]
+pub enum TypeDefault<'a> {
+    Value(Cow<'a, str>),
+    Variant(&'a Enum, &'a EnumKind),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectMeta {
     pub name: String,
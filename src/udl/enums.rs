@@ -1,11 +1,16 @@
-use std::collections::HashMap;
-
+use convert_case::ccase;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enum {
     pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
     pub variants: Vec<EnumKind>,
 }
 
@@ -19,9 +24,12 @@ pub enum EnumKind {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EnumVariant {
     pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub value: EnumVariantValue,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub target_field: Option<String>,
 }
 
@@ -29,7 +37,79 @@ pub struct EnumVariant {
 #[serde(untagged)]
 pub enum EnumVariantValue {
     Single(String),
-    Multiple(HashMap<String, String>),
+    Multiple(IndexMap<String, String>),
+}
+
+impl EnumKind {
+    pub fn id(&self) -> &str {
+        match self {
+            EnumKind::Simple(id) => id,
+            EnumKind::Complex(variant) => &variant.id,
+        }
+    }
+
+    /// Value of the variant on the wire, its single `value:` or else its id. Tagged
+    /// enums use it as the `type` of the variant.
+    pub fn wire_value(&self) -> &str {
+        match self {
+            EnumKind::Complex(EnumVariant {
+                value: EnumVariantValue::Single(value),
+                ..
+            }) => value,
+            variant => variant.id(),
+        }
+    }
+
+    /// Fields of a variant with a map `value:`, by name.
+    pub fn fields(&self) -> Option<&IndexMap<String, String>> {
+        match self {
+            EnumKind::Complex(EnumVariant {
+                value: EnumVariantValue::Multiple(fields),
+                ..
+            }) => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+/// Normalizes a variant reference so `K_CREATED_AT`, `createdAt` and `kCreatedAt`
+/// compare equal.
+fn normalize_variant(name: &str) -> String {
+    let name = ccase!(snake, name);
+    name.strip_prefix("k_").unwrap_or(&name).to_string()
+}
+
+impl Enum {
+    /// Whether any variant has fields. Such enums are unions tagged by `type` on the
+    /// wire, e.g. `{"type": "K_BANK", "account_number": "DE89..."}` or
+    /// `{"type": "card"}`.
+    pub fn is_tagged(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| variant.fields().is_some())
+    }
+
+    /// Resolves a variant reference such as a `default:` value. Matches the variant
+    /// id in any case style, with or without the `K_` prefix, or its single value.
+    pub fn find_variant(&self, name: &str) -> Option<&EnumKind> {
+        self.variants
+            .iter()
+            .find(|variant| variant.id() == name)
+            .or_else(|| {
+                let name = normalize_variant(name);
+                self.variants
+                    .iter()
+                    .find(|variant| normalize_variant(variant.id()) == name)
+            })
+            .or_else(|| {
+                self.variants.iter().find(|variant| {
+                    matches!(variant, EnumKind::Complex(EnumVariant {
+                        value: EnumVariantValue::Single(value),
+                        ..
+                    }) if value == name)
+                })
+            })
+    }
 }
 
 #[cfg(test)]
@@ -49,7 +129,7 @@ mod tests {
 
         assert_eq!(enumm.id, "SortBy");
         assert_eq!(enumm.variants.len(), 3);
-        let mut variant = enumm.variants.get(0).unwrap();
+        let mut variant = enumm.variants.first().unwrap();
         assert_eq!(*variant, EnumKind::Simple("K_ID".into()));
         variant = enumm.variants.get(1).unwrap();
         assert_eq!(*variant, EnumKind::Simple("K_NAME".into()));
@@ -74,7 +154,7 @@ mod tests {
         assert_eq!(enumm.description.unwrap(), "Status of an invoice");
         assert_eq!(enumm.variants.len(), 2);
 
-        let variant = enumm.variants.get(0).unwrap();
+        let variant = enumm.variants.first().unwrap();
         assert_eq!(
             EnumKind::Complex(EnumVariant {
                 id: "K_NOT_FOUND".to_string(),
@@ -100,4 +180,26 @@ mod tests {
             *variant
         );
     }
+
+    #[test]
+    fn test_find_variant() {
+        let enumm: Enum = serde_yaml::from_str(
+            r#"
+            id: SortBy
+            variants:
+              - K_ID
+              - K_CREATED_AT
+              - id: K_DRAFT
+                value: "draft"
+        "#,
+        )
+        .unwrap();
+        let find = |name| enumm.find_variant(name).map(EnumKind::id);
+        assert_eq!(find("K_ID"), Some("K_ID"));
+        assert_eq!(find("createdAt"), Some("K_CREATED_AT"));
+        assert_eq!(find("kCreatedAt"), Some("K_CREATED_AT"));
+        assert_eq!(find("created_at"), Some("K_CREATED_AT"));
+        assert_eq!(find("draft"), Some("K_DRAFT"));
+        assert_eq!(find("name"), None);
+    }
 }
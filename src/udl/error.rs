@@ -0,0 +1,384 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::udl::span::{PathSegment, Span};
+
+#[derive(Debug, Error)]
+pub enum UdlError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse `{}`: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[error("failed to parse `{}`: {message}", path.display())]
+    Syntax {
+        path: PathBuf,
+        message: String,
+        span: Span,
+    },
+
+    #[error("failed to serialize manifest: {0}")]
+    Serialize(#[source] serde_yaml::Error),
+
+    #[error("failed to run `{tool}` on `{path}`: {source}")]
+    Format {
+        tool: String,
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("model `{model}`, property `{property}`: missing `type`")]
+    MissingType { model: String, property: String },
+
+    #[error("model `{model}`, property `{property}`: invalid limit `{value}`")]
+    InvalidLimit {
+        model: String,
+        property: String,
+        value: String,
+    },
+
+    #[error("model `{model}`, property `{property}`: invalid `{key}` value `{value}`")]
+    InvalidNumber {
+        model: String,
+        property: String,
+        key: String,
+        value: String,
+    },
+
+    #[error("model `{model}`, property `{property}`: unknown format `{value}`")]
+    UnknownFormat {
+        model: String,
+        property: String,
+        value: String,
+    },
+
+    #[error("model `{model}`, property `{property}`: invalid pattern `{value}`: {reason}")]
+    InvalidPattern {
+        model: String,
+        property: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("model `{model}`: error enum `{error}` is not defined")]
+    MissingErrorEnum { model: String, error: String },
+
+    #[error("duplicate {kind} id `{id}`")]
+    DuplicateId {
+        kind: &'static str,
+        id: String,
+        index: usize,
+    },
+
+    #[error("enum `{enum_id}`: duplicate variant `{variant}`")]
+    DuplicateVariant { enum_id: String, variant: String },
+
+    #[error("model `{model}`, property `{property}`: unknown type `{type_name}`")]
+    UnknownType {
+        model: String,
+        property: String,
+        type_name: String,
+    },
+
+    #[error("model `{model}`, property `{property}`: unknown enum `{enum_id}`")]
+    UnknownEnum {
+        model: String,
+        property: String,
+        enum_id: String,
+    },
+
+    #[error(
+        "model `{model}`, property `{property}`: `{type_name}` cannot be part of a set element or map key"
+    )]
+    UnhashableType {
+        model: String,
+        property: String,
+        type_name: String,
+    },
+
+    #[error(
+        "model `{model}`, property `{property}`: default `{value}` is not a valid `{type_name}`"
+    )]
+    InvalidDefault {
+        model: String,
+        property: String,
+        value: String,
+        type_name: String,
+    },
+
+    #[error("enum `{enum_id}`, variant `{variant}`: field `{field}` collides with the `type` tag")]
+    TagField {
+        enum_id: String,
+        variant: String,
+        field: String,
+    },
+
+    #[error(
+        "enum `{enum_id}`, variant `{variant}`: target `{target}` does not match any validator"
+    )]
+    UnknownTarget {
+        enum_id: String,
+        variant: String,
+        target: String,
+    },
+
+    #[error(
+        "enum `{enum_id}`, variant `{variant}`: `target_field` `{field}` is not a property of a model using it"
+    )]
+    UnknownTargetField {
+        enum_id: String,
+        variant: String,
+        field: String,
+    },
+
+    #[error("model `{model}`, property `{property}`: invalid type `{value}`")]
+    InvalidType {
+        model: String,
+        property: String,
+        value: String,
+    },
+
+    #[error("defaults: unknown type `{type_name}`")]
+    UnknownDefaultType { type_name: String },
+
+    #[error("defaults: `{value}` is not a valid `{type_name}`")]
+    InvalidTypeDefault { type_name: String, value: String },
+
+    #[error("enum `{enum_id}`: default `{value}` is not a variant")]
+    InvalidEnumDefault { enum_id: String, value: String },
+
+    #[error("enum `{enum_id}`: default `{value}` is a variant with fields")]
+    EnumDefaultWithFields { enum_id: String, value: String },
+
+    #[error("interface `{interface}`, method `{method}`: duplicate method")]
+    DuplicateMethod { interface: String, method: String },
+
+    #[error(
+        "interface `{interface}`, method `{method}`: `{field}` references unknown type `{type_name}`"
+    )]
+    UnknownMethodType {
+        interface: String,
+        method: String,
+        field: &'static str,
+        type_name: String,
+    },
+
+    #[error("interface `{interface}`, method `{method}`: invalid params `{value}`")]
+    InvalidParams {
+        interface: String,
+        method: String,
+        value: String,
+    },
+
+    #[error("interface `{interface}`, method `{method}`: invalid return type `{value}`")]
+    InvalidReturnType {
+        interface: String,
+        method: String,
+        value: String,
+    },
+
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    #[error("not formatted: {}", paths.join(", "))]
+    Unformatted { paths: Vec<String> },
+
+    #[error("`{}`: {count} problem(s) found", path.display())]
+    Invalid { path: PathBuf, count: usize },
+
+    #[error("{count} manifest(s) could not be processed")]
+    Failed { count: usize },
+}
+
+impl UdlError {
+    /// Position reported directly by the YAML parser.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            UdlError::Parse { source, .. } => source.location().map(|location| Span {
+                line: location.line(),
+                column: location.column(),
+            }),
+            UdlError::Syntax { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// Path to the manifest node the error refers to.
+    pub fn span_path(&self) -> Option<Vec<PathSegment<'_>>> {
+        use PathSegment::*;
+        match self {
+            UdlError::UnknownType {
+                model, property, ..
+            }
+            | UdlError::UnknownEnum {
+                model, property, ..
+            }
+            | UdlError::UnhashableType {
+                model, property, ..
+            }
+            | UdlError::InvalidType {
+                model, property, ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key("type"),
+            ]),
+            UdlError::InvalidDefault {
+                model, property, ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key("default"),
+            ]),
+            UdlError::DuplicateId { kind, index, .. } => Some(vec![
+                Key(match *kind {
+                    "enum" => "enums",
+                    "interface" => "interface",
+                    _ => "models",
+                }),
+                Index(*index),
+            ]),
+            UdlError::UnknownDefaultType { type_name }
+            | UdlError::InvalidTypeDefault { type_name, .. } => {
+                Some(vec![Key("defaults"), Key(type_name)])
+            }
+            UdlError::InvalidEnumDefault { enum_id, .. }
+            | UdlError::EnumDefaultWithFields { enum_id, .. } => {
+                Some(vec![Key("enums"), Id(enum_id), Key("default")])
+            }
+            UdlError::DuplicateMethod { interface, method } => Some(vec![
+                Key("interface"),
+                Id(interface),
+                Key("methods"),
+                Id(method),
+            ]),
+            UdlError::UnknownMethodType {
+                interface,
+                method,
+                field,
+                ..
+            } => Some(vec![
+                Key("interface"),
+                Id(interface),
+                Key("methods"),
+                Id(method),
+                Key(field),
+            ]),
+            UdlError::InvalidReturnType {
+                interface, method, ..
+            } => Some(vec![
+                Key("interface"),
+                Id(interface),
+                Key("methods"),
+                Id(method),
+                Key("returns"),
+            ]),
+            UdlError::InvalidParams {
+                interface, method, ..
+            } => Some(vec![
+                Key("interface"),
+                Id(interface),
+                Key("methods"),
+                Id(method),
+                Key("params"),
+            ]),
+            UdlError::DuplicateVariant { enum_id, variant } => Some(vec![
+                Key("enums"),
+                Id(enum_id),
+                Key("variants"),
+                Id(variant),
+            ]),
+            UdlError::TagField {
+                enum_id,
+                variant,
+                field,
+            } => Some(vec![
+                Key("enums"),
+                Id(enum_id),
+                Key("variants"),
+                Id(variant),
+                Key("value"),
+                Name(field),
+            ]),
+            UdlError::UnknownTarget {
+                enum_id, variant, ..
+            } => Some(vec![
+                Key("enums"),
+                Id(enum_id),
+                Key("variants"),
+                Id(variant),
+                Key("target"),
+            ]),
+            UdlError::UnknownTargetField {
+                enum_id, variant, ..
+            } => Some(vec![
+                Key("enums"),
+                Id(enum_id),
+                Key("variants"),
+                Id(variant),
+                Key("target_field"),
+            ]),
+            UdlError::MissingType { model, property } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Name(property),
+            ]),
+            UdlError::InvalidLimit {
+                model, property, ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key("limit"),
+            ]),
+            UdlError::InvalidNumber {
+                model,
+                property,
+                key,
+                ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key(key),
+            ]),
+            UdlError::UnknownFormat {
+                model, property, ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key("format"),
+            ]),
+            UdlError::InvalidPattern {
+                model, property, ..
+            } => Some(vec![
+                Key("models"),
+                Id(model),
+                Key("properties"),
+                Key(property),
+                Key("pattern"),
+            ]),
+            UdlError::MissingErrorEnum { model, .. } => {
+                Some(vec![Key("models"), Id(model), Key("error")])
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,15 +1,28 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
-use crate::udl::{UDL, class::Class, enums::Enum};
+use crate::udl::{UDL, UdlError, class::Class, enums::Enum, interface::Interface};
 
 pub trait LangGenerator {
     fn extension(&self) -> &str;
 
-    fn gen_enum(&self, enumm: &Enum) -> String;
+    fn gen_enum(&self, udl: &UDL, enumm: &Enum) -> (String, BTreeSet<String>);
 
-    fn gen_class(&self, class: &Class, error_enum: Option<&Enum>) -> (String, HashSet<String>);
+    fn gen_class(
+        &self,
+        udl: &UDL,
+        class: &Class,
+        error_enum: Option<&Enum>,
+    ) -> Result<(String, BTreeSet<String>), UdlError>;
 
-    fn generate(&self, udl: &UDL) -> String;
+    /// Generates a service definition. Targets without one emit nothing.
+    fn gen_interface(
+        &self,
+        _interface: &Interface,
+    ) -> Result<(String, BTreeSet<String>), UdlError> {
+        Ok((String::new(), BTreeSet::new()))
+    }
 
-    fn format(&self, path: &str);
+    fn generate(&self, udl: &UDL) -> Result<String, UdlError>;
+
+    fn format(&self, path: &str) -> Result<(), UdlError>;
 }
@@ -0,0 +1,208 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::udl::{
+    types::{TypeExpr, TypeKind},
+    utils::parse_type,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub methods: Vec<Method>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Method {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<MethodParams>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub returns: Option<String>,
+    #[serde(rename = "async", default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum MethodParams {
+    /// A single model or primitive argument, e.g. `params: LoginRequest`
+    Type(String),
+    /// Named arguments, e.g. `params: { id: string }`
+    Inline(IndexMap<String, String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReturnType {
+    Void,
+    Type(TypeExpr),
+    /// `Result<T, E>`, with no `ok` type for `Result<void, E>`
+    Result {
+        ok: Option<TypeExpr>,
+        err: TypeExpr,
+    },
+}
+
+impl Method {
+    /// Parses `returns:`, treating a missing value as `void`. Returns `None` for a
+    /// malformed type or a `Result` that does not have exactly an ok and an error type.
+    pub fn return_type(&self) -> Option<ReturnType> {
+        let ty = parse_type(self.returns.as_deref().unwrap_or("void"))?;
+        match ty.kind {
+            TypeKind::Void if !ty.nullable => Some(ReturnType::Void),
+            TypeKind::Generic { name, args } if name == "Result" && !ty.nullable => {
+                let [ok, err] = <[TypeExpr; 2]>::try_from(args).ok()?;
+                Some(ReturnType::Result {
+                    ok: (ok.kind != TypeKind::Void).then_some(ok),
+                    err,
+                })
+            }
+            _ => Some(ReturnType::Type(ty)),
+        }
+    }
+
+    /// Parsed param types, with their names for inline params. Fails with the first
+    /// malformed type.
+    pub fn param_types(&self) -> Result<Vec<(Option<&str>, TypeExpr)>, &str> {
+        fn parse(ty: &str) -> Result<TypeExpr, &str> {
+            parse_type(ty).ok_or(ty)
+        }
+        match &self.params {
+            None => Ok(vec![]),
+            Some(MethodParams::Type(ty)) => Ok(vec![(None, parse(ty)?)]),
+            Some(MethodParams::Inline(params)) => params
+                .iter()
+                .map(|(name, ty)| Ok((Some(name.as_str()), parse(ty)?)))
+                .collect(),
+        }
+    }
+}
+
+/// Name of a method param before case conversion: its own name when inline, `value`
+/// for a single primitive and the type name for a single model.
+pub fn param_name<'a>(name: Option<&'a str>, ty: &'a TypeExpr) -> &'a str {
+    match (name, &ty.kind) {
+        (Some(name), _) => name,
+        (None, TypeKind::Primitive(_)) => "value",
+        (None, _) => ty.name(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interface_parse() {
+        let test_1 = r#"
+            id: InvoiceService
+            description: "Invoice management operations"
+            tags:
+              - billing
+            methods:
+              - id: fetch_invoices
+                description: "Fetch invoices with optional filtering"
+                params: FetchInvoicesRequest
+                returns: Result<List<Invoice>, $enum::InvoiceStatus>
+                async: true
+
+              - id: get_invoice
+                params:
+                  id: string
+                  include_items: bool
+                returns: Result<Invoice, $enum::InvoiceStatus>
+                async: true
+
+              - id: delete_invoice
+                params: string
+                returns: void
+
+              - id: ping
+        "#;
+        let interface: Interface = serde_yaml::from_str(test_1).unwrap();
+        assert_eq!(interface.id, "InvoiceService");
+        assert_eq!(
+            interface.description.as_deref(),
+            Some("Invoice management operations")
+        );
+        assert_eq!(interface.tags, vec!["billing"]);
+        assert_eq!(interface.methods.len(), 4);
+
+        let method = &interface.methods[0];
+        assert_eq!(method.id, "fetch_invoices");
+        assert!(method.is_async);
+        assert_eq!(
+            method.params,
+            Some(MethodParams::Type("FetchInvoicesRequest".to_string()))
+        );
+        assert_eq!(
+            method.return_type(),
+            Some(ReturnType::Result {
+                ok: parse_type("List<Invoice>"),
+                err: parse_type("$enum::InvoiceStatus").unwrap()
+            })
+        );
+
+        let method = &interface.methods[1];
+        assert_eq!(
+            method.params,
+            Some(MethodParams::Inline(IndexMap::from([
+                ("id".to_string(), "string".to_string()),
+                ("include_items".to_string(), "bool".to_string()),
+            ])))
+        );
+        if let Some(MethodParams::Inline(map)) = &method.params {
+            assert_eq!(map.keys().collect::<Vec<_>>(), vec!["id", "include_items"]);
+        }
+
+        let method = &interface.methods[2];
+        assert!(!method.is_async);
+        assert_eq!(
+            method.params,
+            Some(MethodParams::Type("string".to_string()))
+        );
+        assert_eq!(method.return_type(), Some(ReturnType::Void));
+
+        let method = &interface.methods[3];
+        assert_eq!(method.params, None);
+        assert_eq!(method.return_type(), Some(ReturnType::Void));
+    }
+
+    #[test]
+    fn test_return_type() {
+        let method = |returns: &str| Method {
+            id: "test".to_string(),
+            description: None,
+            params: None,
+            returns: Some(returns.to_string()),
+            is_async: false,
+        };
+        assert_eq!(
+            method("Result<Map<string, User>, ApiError>").return_type(),
+            Some(ReturnType::Result {
+                ok: parse_type("Map<string, User>"),
+                err: parse_type("ApiError").unwrap()
+            })
+        );
+        assert_eq!(
+            method("List<User>").return_type(),
+            Some(ReturnType::Type(parse_type("List<User>").unwrap()))
+        );
+        assert_eq!(
+            method("Result<void, ApiError>").return_type(),
+            Some(ReturnType::Result {
+                ok: None,
+                err: parse_type("ApiError").unwrap()
+            })
+        );
+        assert_eq!(method("Result<User>").return_type(), None);
+        assert_eq!(method("Result<User, A, B>").return_type(), None);
+        assert_eq!(method("Result<User, >").return_type(), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+
+use crate::udl::{
+    UDL, UdlError,
+    span::{Node, Span, Spanned},
+};
+
+/// Manifest source text as read from disk.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+/// Parsed manifest, with the spanned YAML tree kept next to the typed model.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub udl: UDL,
+    pub spans: Spanned,
+}
+
+impl SourceFile {
+    pub fn read(path: &Path) -> Result<SourceFile, UdlError> {
+        let text = std::fs::read_to_string(path).map_err(|source| UdlError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(SourceFile {
+            path: path.to_path_buf(),
+            text,
+        })
+    }
+
+    pub fn parse(&self) -> Result<Manifest, UdlError> {
+        let udl: UDL = serde_yaml::from_str(&self.text).map_err(|source| UdlError::Parse {
+            path: self.path.clone(),
+            source,
+        })?;
+        let spans = Spanned::parse(&self.text).map_err(|err| UdlError::Syntax {
+            path: self.path.clone(),
+            message: err.to_string(),
+            span: Span::from(*err.marker()),
+        })?;
+        Ok(Manifest { udl, spans })
+    }
+
+    /// Renders `err` rustc-style, with a caret snippet of the offending line when
+    /// the error can be located in this file.
+    pub fn render(&self, err: &UdlError, spans: Option<&Spanned>) -> String {
+        let located = match (err.span(), err.span_path(), spans) {
+            (Some(span), _, _) => Some((span, 1)),
+            (None, Some(path), Some(spans)) => {
+                let node = spans.lookup(&path);
+                let len = match &node.node {
+                    Node::Scalar(value) => value.chars().count(),
+                    _ => 1,
+                };
+                Some((node.span, len))
+            }
+            _ => None,
+        };
+
+        let mut out = format!("error: {}\n", err);
+        let Some((span, len)) = located else {
+            return out;
+        };
+        let line_no = span.line.to_string();
+        let gutter = " ".repeat(line_no.len());
+        out.push_str(&format!(
+            "{}--> {}:{}:{}\n",
+            gutter,
+            self.path.display(),
+            span.line,
+            span.column
+        ));
+        if let Some(line) = self.text.lines().nth(span.line.saturating_sub(1)) {
+            let available = line.chars().count().saturating_sub(span.column - 1);
+            out.push_str(&format!("{} |\n", gutter));
+            out.push_str(&format!("{} | {}\n", line_no, line));
+            out.push_str(&format!(
+                "{} | {}{}\n",
+                gutter,
+                " ".repeat(span.column - 1),
+                "^".repeat(len.clamp(1, available.max(1)))
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let file = SourceFile {
+            path: PathBuf::from("app.yaml"),
+            text: std::fs::read_to_string("examples/billing_app.yaml")
+                .unwrap()
+                .replace("limit: 8...32", "limit: 8...x"),
+        };
+        let manifest = file.parse().unwrap();
+        let err = UdlError::InvalidLimit {
+            model: "LoginRequest".to_string(),
+            property: "password".to_string(),
+            value: "8...x".to_string(),
+        };
+        let line = file
+            .text
+            .lines()
+            .position(|line| line.contains("limit: 8...x"))
+            .unwrap()
+            + 1;
+        assert_eq!(
+            file.render(&err, Some(&manifest.spans)),
+            format!(
+                "error: model `LoginRequest`, property `password`: invalid limit `8...x`\n\
+                 {0:w$}--> app.yaml:{1}:16\n\
+                 {0:w$} |\n\
+                 {1} |         limit: 8...x\n\
+                 {0:w$} |                ^^^^^\n",
+                "",
+                line,
+                w = line.to_string().len()
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_parse_error() {
+        let file = SourceFile {
+            path: PathBuf::from("bad.yaml"),
+            text: "udl_version: 0.0.1\nproject: 3\n".to_string(),
+        };
+        let err = file.parse().unwrap_err();
+        let rendered = file.render(&err, None);
+        assert!(rendered.contains(" --> bad.yaml:2:10\n"), "{}", rendered);
+        assert!(
+            rendered.ends_with("2 | project: 3\n  |          ^\n"),
+            "{}",
+            rendered
+        );
+    }
+}
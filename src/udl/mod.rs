@@ -1,8 +1,16 @@
 pub mod class;
+pub mod constraints;
 pub mod core;
 pub mod enums;
+pub mod error;
 pub mod generator;
+pub mod interface;
+pub mod manifest;
+pub mod span;
+pub mod types;
 pub mod utils;
+pub mod validator;
 
 pub(crate) use core::UDL;
+pub(crate) use error::UdlError;
 pub(crate) use generator::LangGenerator;
@@ -0,0 +1,229 @@
+use yaml_rust::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::{Marker, ScanError},
+};
+
+/// 1-based line and column of a node in the manifest source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Marker> for Span {
+    fn from(marker: Marker) -> Self {
+        Span {
+            line: marker.line(),
+            column: marker.col() + 1,
+        }
+    }
+}
+
+/// A YAML node together with the position it was declared at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub span: Span,
+    pub node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Scalar(String),
+    Sequence(Vec<Spanned>),
+    Mapping(Vec<(Spanned, Spanned)>),
+}
+
+/// One step of a path into the manifest, used to locate the node an error refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// Value of a mapping entry.
+    Key(&'a str),
+    /// Key of a mapping entry, i.e. the name itself.
+    Name(&'a str),
+    /// Element of a sequence whose `id` entry equals the given value.
+    Id(&'a str),
+    /// Element of a sequence by position.
+    Index(usize),
+}
+
+impl Spanned {
+    /// Parses the first document of `source`, keeping the position of every node.
+    pub fn parse(source: &str) -> Result<Spanned, ScanError> {
+        let mut builder = Builder::default();
+        Parser::new(source.chars()).load(&mut builder, false)?;
+        Ok(builder.root.unwrap_or(Spanned {
+            span: Span { line: 1, column: 1 },
+            node: Node::Scalar(String::new()),
+        }))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.node {
+            Node::Scalar(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn entry(&self, key: &str) -> Option<&(Spanned, Spanned)> {
+        match &self.node {
+            Node::Mapping(entries) => entries.iter().find(|(k, _)| k.as_str() == Some(key)),
+            _ => None,
+        }
+    }
+
+    /// Value of the mapping entry named `key`.
+    pub fn get(&self, key: &str) -> Option<&Spanned> {
+        self.entry(key).map(|(_, value)| value)
+    }
+
+    /// Sequence element whose `id` entry equals `id`.
+    pub fn find_id(&self, id: &str) -> Option<&Spanned> {
+        match &self.node {
+            Node::Sequence(items) => items
+                .iter()
+                .find(|item| item.get("id").and_then(Spanned::as_str) == Some(id)),
+            _ => None,
+        }
+    }
+
+    /// Follows `path` as far as it resolves and returns the node to report for the
+    /// deepest step reached. Collections are reported at their key or `id`, since the
+    /// parser positions them after the preceding key.
+    pub fn lookup(&self, path: &[PathSegment]) -> &Spanned {
+        let mut node = self;
+        let mut report = self;
+        for segment in path {
+            let next = match segment {
+                PathSegment::Key(key) => node.entry(key).map(|(key, value)| {
+                    let report = if value.as_str().is_some() { value } else { key };
+                    (value, report)
+                }),
+                PathSegment::Name(key) => node.entry(key).map(|(key, _)| (key, key)),
+                PathSegment::Id(id) => node
+                    .find_id(id)
+                    .map(|item| (item, item.get("id").unwrap_or(item))),
+                PathSegment::Index(index) => match &node.node {
+                    Node::Sequence(items) => items
+                        .get(*index)
+                        .map(|item| (item, item.get("id").unwrap_or(item))),
+                    _ => None,
+                },
+            };
+            match next {
+                Some(next) => (node, report) = next,
+                None => break,
+            }
+        }
+        report
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    stack: Vec<(Span, Frame)>,
+    root: Option<Spanned>,
+}
+
+enum Frame {
+    Sequence(Vec<Spanned>),
+    Mapping(Vec<(Spanned, Spanned)>, Option<Spanned>),
+}
+
+impl Builder {
+    fn push(&mut self, value: Spanned) {
+        match self.stack.last_mut() {
+            None => {
+                if self.root.is_none() {
+                    self.root = Some(value);
+                }
+            }
+            Some((_, Frame::Sequence(items))) => items.push(value),
+            Some((_, Frame::Mapping(entries, pending))) => match pending.take() {
+                Some(key) => entries.push((key, value)),
+                None => *pending = Some(value),
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for Builder {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        let span = Span::from(mark);
+        match ev {
+            Event::Scalar(value, ..) => self.push(Spanned {
+                span,
+                node: Node::Scalar(value),
+            }),
+            Event::Alias(_) => self.push(Spanned {
+                span,
+                node: Node::Scalar(String::new()),
+            }),
+            Event::SequenceStart(_) => self.stack.push((span, Frame::Sequence(Vec::new()))),
+            Event::MappingStart(_) => self.stack.push((span, Frame::Mapping(Vec::new(), None))),
+            Event::SequenceEnd | Event::MappingEnd => {
+                if let Some((span, frame)) = self.stack.pop() {
+                    let node = match frame {
+                        Frame::Sequence(items) => Node::Sequence(items),
+                        Frame::Mapping(entries, _) => Node::Mapping(entries),
+                    };
+                    self.push(Spanned { span, node });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let source = r#"
+models:
+  - id: User
+    properties:
+      name: string
+  - id: LoginRequest
+    properties:
+      password:
+        type: string
+        limit: 8...32
+"#;
+        let spans = Spanned::parse(source).unwrap();
+        let limit = spans.lookup(&[
+            PathSegment::Key("models"),
+            PathSegment::Id("LoginRequest"),
+            PathSegment::Key("properties"),
+            PathSegment::Key("password"),
+            PathSegment::Key("limit"),
+        ]);
+        assert_eq!(
+            limit.span,
+            Span {
+                line: 10,
+                column: 16
+            }
+        );
+        assert_eq!(limit.as_str(), Some("8...32"));
+
+        let name = spans.lookup(&[
+            PathSegment::Key("models"),
+            PathSegment::Id("User"),
+            PathSegment::Key("properties"),
+            PathSegment::Name("name"),
+        ]);
+        assert_eq!(name.span, Span { line: 5, column: 7 });
+
+        // Unresolved segments stop at the key of the deepest node found
+        let missing = spans.lookup(&[
+            PathSegment::Key("models"),
+            PathSegment::Id("User"),
+            PathSegment::Key("properties"),
+            PathSegment::Key("email"),
+        ]);
+        assert_eq!(missing.span, Span { line: 4, column: 5 });
+        assert_eq!(missing.as_str(), Some("properties"));
+    }
+}
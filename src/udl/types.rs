@@ -0,0 +1,108 @@
+use std::fmt;
+
+/// Parsed UDL type expression such as `Map<string, List<$enum::Status?>>^`, see
+/// [`parse_type`](crate::udl::utils::parse_type).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeExpr {
+    pub kind: TypeKind,
+    /// `?` suffix, the value may be null
+    pub nullable: bool,
+    /// `^` suffix, the value may be omitted
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    /// One of [`PRIMITIVES`](crate::udl::utils::PRIMITIVES), e.g. `string`
+    Primitive(String),
+    /// Reference to a model, e.g. `InvoiceItem`
+    Model(String),
+    /// Reference to an enum, e.g. `$enum::InvoiceStatus`
+    Enum(String),
+    /// `void`, only meaningful as a method return type or `Result` ok type
+    Void,
+    /// `List<T>`
+    List(Box<TypeExpr>),
+    /// `Set<T>`
+    Set(Box<TypeExpr>),
+    /// `Map<K, V>`
+    Map(Box<TypeExpr>, Box<TypeExpr>),
+    /// Any other type with arguments, e.g. `Result<User, ApiError>`
+    Generic { name: String, args: Vec<TypeExpr> },
+}
+
+impl TypeExpr {
+    /// Name of the type without arguments, markers or `$enum::` prefix.
+    pub fn name(&self) -> &str {
+        match &self.kind {
+            TypeKind::Primitive(name)
+            | TypeKind::Model(name)
+            | TypeKind::Enum(name)
+            | TypeKind::Generic { name, .. } => name,
+            TypeKind::List(_) => "List",
+            TypeKind::Set(_) => "Set",
+            TypeKind::Map(..) => "Map",
+            TypeKind::Void => "void",
+        }
+    }
+
+    /// Type arguments, e.g. the key and value type of a `Map`.
+    pub fn args(&self) -> Vec<&TypeExpr> {
+        match &self.kind {
+            TypeKind::List(item) | TypeKind::Set(item) => vec![item],
+            TypeKind::Map(key, value) => vec![key, value],
+            TypeKind::Generic { args, .. } => args.iter().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Whether this is one of the integer or floating point primitives.
+    pub fn is_number(&self) -> bool {
+        match &self.kind {
+            TypeKind::Primitive(name) => {
+                name.starts_with("int")
+                    || name.starts_with("uint")
+                    || name.starts_with("float")
+                    || name == "isize"
+                    || name == "usize"
+            }
+            _ => false,
+        }
+    }
+
+    /// Calls `f` on this type and every type argument nested in it.
+    pub fn visit<'a>(&'a self, f: &mut impl FnMut(&'a TypeExpr)) {
+        f(self);
+        for arg in self.args() {
+            arg.visit(f);
+        }
+    }
+}
+
+impl fmt::Display for TypeExpr {
+    /// Formats the type back into UDL syntax.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Primitive(name) | TypeKind::Model(name) => write!(f, "{}", name)?,
+            TypeKind::Enum(name) => write!(f, "$enum::{}", name)?,
+            TypeKind::Void => write!(f, "void")?,
+            _ => {
+                write!(f, "{}<", self.name())?;
+                for (i, arg) in self.args().into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ">")?;
+            }
+        }
+        if self.nullable {
+            write!(f, "?")?;
+        }
+        if self.optional {
+            write!(f, "^")?;
+        }
+        Ok(())
+    }
+}
@@ -1,48 +1,167 @@
-use crate::udl::enums::{Enum, EnumKind, EnumVariant};
+use std::collections::{BTreeSet, HashMap};
 
-// pub fn is_optional_type(type_name: &str) -> bool {
-//     type_name.contains("^")
-// }
+use crate::udl::{
+    enums::{Enum, EnumKind, EnumVariant},
+    types::{TypeExpr, TypeKind},
+};
 
-pub fn is_nullable_type(type_name: &str) -> bool {
-    type_name.contains("?")
-}
-
-// pub fn is_enum_type(type_name: &str) -> bool {
-//     type_name.starts_with("$enum::")
-// }
+/// Built-in scalar types every generator maps.
+pub const PRIMITIVES: [&str; 25] = [
+    "bool", "int", "float", "string", "isize", "int8", "int16", "int32", "int64", "usize", "uint8",
+    "uint16", "uint32", "uint64", "float32", "float64", "datetime", "date", "object", "uuid",
+    "decimal", "bytes", "url", "duration", "email",
+];
 
-pub fn parse_limit_validator(value: &str) -> (isize, isize, isize) {
+/// Parses `min...max` and `min..default..max` style limits into `(min, max, default)`,
+/// using `-1` for absent bounds. Returns `None` for malformed limits.
+pub fn parse_limit_validator(value: &str) -> Option<(isize, isize, isize)> {
     if value.contains("...") {
         let parts: Vec<&str> = value.split("...").collect();
-        let min = parts[0].parse().unwrap_or(-1);
-        let max = parts[1].parse().unwrap_or(-1);
+        if parts.len() != 2 {
+            return None;
+        }
+        let min = parse_bound(parts[0], -1)?;
+        let max = parse_bound(parts[1], -1)?;
         let default = -1;
-        (min, max, default)
+        Some((min, max, default))
     } else {
         let parts: Vec<&str> = value.split("..").collect();
-        #[cfg(debug_assertions)]
-        println!("{:?}", parts);
         if parts.len() < 3 {
-            return if value.starts_with("..") {
-                let max = parts[1].parse().unwrap_or(-1);
+            return if value.starts_with("..") && parts.len() == 2 {
+                let max = parse_bound(parts[1], -1)?;
                 let default = max;
-                (-1, max, default)
-            } else if value.ends_with("..") {
-                let min = parts[0].parse().unwrap_or(-1);
+                Some((-1, max, default))
+            } else if value.ends_with("..") && parts.len() == 2 {
+                let min = parse_bound(parts[0], -1)?;
                 let default = min;
-                (min, -1, default)
+                Some((min, -1, default))
             } else {
-                panic!("Invalid limit validator format");
+                None
             };
         }
-        let min = parts[0].parse().unwrap_or(0);
-        let default = parts[1].parse().unwrap();
-        let max = parts[2].parse().unwrap_or(-1);
-        (min, max, default)
+        if parts.len() > 3 {
+            return None;
+        }
+        let min = parse_bound(parts[0], 0)?;
+        let default = parts[1].trim().parse().ok()?;
+        let max = parse_bound(parts[2], -1)?;
+        Some((min, max, default))
+    }
+}
+
+/// Parses one side of a limit, falling back to `empty` when it is omitted.
+fn parse_bound(value: &str, empty: isize) -> Option<isize> {
+    let value = value.trim();
+    if value.is_empty() {
+        Some(empty)
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Parses a type expression such as `Map<string, List<$enum::Status?>>^`. Names that
+/// are not primitives or `void` are model references. Returns `None` for malformed types,
+/// including collections with the wrong number of arguments.
+pub fn parse_type(value: &str) -> Option<TypeExpr> {
+    let mut parser = TypeParser { rest: value };
+    let ty = parser.parse()?;
+    parser.rest.trim().is_empty().then_some(ty)
+}
+
+struct TypeParser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> TypeParser<'a> {
+    fn eat(&mut self, token: &str) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start();
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+        if end == 0 || self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let (ident, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(ident)
+    }
+
+    fn parse(&mut self) -> Option<TypeExpr> {
+        let kind = if self.eat("$enum::") {
+            TypeKind::Enum(self.ident()?.to_string())
+        } else {
+            let name = self.ident()?;
+            if self.eat("<") {
+                let mut args = vec![self.parse()?];
+                while self.eat(",") {
+                    args.push(self.parse()?);
+                }
+                if !self.eat(">") {
+                    return None;
+                }
+                let mut args = args.into_iter().map(Box::new);
+                match (name, args.len()) {
+                    ("List", 1) => TypeKind::List(args.next()?),
+                    ("Set", 1) => TypeKind::Set(args.next()?),
+                    ("Map", 2) => TypeKind::Map(args.next()?, args.next()?),
+                    ("List" | "Set" | "Map", _) => return None,
+                    _ => TypeKind::Generic {
+                        name: name.to_string(),
+                        args: args.map(|arg| *arg).collect(),
+                    },
+                }
+            } else if name == "void" {
+                TypeKind::Void
+            } else if PRIMITIVES.contains(&name) {
+                TypeKind::Primitive(name.to_string())
+            } else {
+                TypeKind::Model(name.to_string())
+            }
+        };
+        let mut ty = TypeExpr {
+            kind,
+            nullable: false,
+            optional: false,
+        };
+        loop {
+            if !ty.nullable && self.eat("?") {
+                ty.nullable = true;
+            } else if !ty.optional && self.eat("^") {
+                ty.optional = true;
+            } else {
+                return Some(ty);
+            }
+        }
     }
 }
 
+/// Adds the imports `table` lists for the primitives of `ty`, type arguments included.
+pub fn primitive_imports(
+    ty: &TypeExpr,
+    table: &HashMap<&str, &str>,
+    imports: &mut BTreeSet<String>,
+) {
+    ty.visit(&mut |ty| {
+        if let TypeKind::Primitive(name) = &ty.kind
+            && let Some(import) = table.get(name.as_str())
+        {
+            imports.insert(import.to_string());
+        }
+    });
+}
+
 pub fn extract_enum_variant<'a>(
     enumm: &'a Enum,
     filter: &str,
@@ -71,19 +190,81 @@ mod tests {
 
     #[test]
     fn test_is_nullable() {
-        assert!(is_nullable_type("String?"));
-        assert!(!is_nullable_type("String"));
-        assert!(is_nullable_type("int?^"));
-        assert!(!is_nullable_type("bool^"));
+        let nullable = |value| parse_type(value).unwrap().nullable;
+        assert!(nullable("String?"));
+        assert!(!nullable("String"));
+        assert!(nullable("int?^"));
+        assert!(!nullable("bool^"));
+        assert!(!nullable("List<string?>"));
+    }
+
+    #[test]
+    fn test_parse_type() {
+        let ty = |kind, nullable, optional| TypeExpr {
+            kind,
+            nullable,
+            optional,
+        };
+        let string = || Box::new(ty(TypeKind::Primitive("string".to_string()), false, false));
+        assert_eq!(
+            parse_type("string^"),
+            Some(ty(TypeKind::Primitive("string".to_string()), false, true))
+        );
+        assert_eq!(
+            parse_type("Map<string, List<$enum::Status?>>?"),
+            Some(ty(
+                TypeKind::Map(
+                    string(),
+                    Box::new(ty(
+                        TypeKind::List(Box::new(ty(
+                            TypeKind::Enum("Status".to_string()),
+                            true,
+                            false
+                        ))),
+                        false,
+                        false
+                    ))
+                ),
+                true,
+                false
+            ))
+        );
+        assert_eq!(parse_type("void"), Some(ty(TypeKind::Void, false, false)));
+        assert_eq!(
+            parse_type("Set<string>"),
+            Some(ty(TypeKind::Set(string()), false, false))
+        );
+        let result = parse_type(" Result<List<Invoice>,$enum::InvoiceStatus> ").unwrap();
+        assert!(matches!(result.kind, TypeKind::Generic { .. }));
+        assert_eq!(
+            result.to_string(),
+            "Result<List<Invoice>, $enum::InvoiceStatus>"
+        );
+        for invalid in [
+            "",
+            "List<",
+            "List<>",
+            "Map<string,>",
+            "string??",
+            "1abc",
+            "a b",
+            "List<string, int>",
+            "Map<string>",
+        ] {
+            assert_eq!(parse_type(invalid), None, "{}", invalid);
+        }
     }
 
     #[test]
     fn test_parse_limit_validator() {
-        assert_eq!(parse_limit_validator("1...10"), (1, 10, -1));
-        assert_eq!(parse_limit_validator("...10"), (-1, 10, -1));
-        assert_eq!(parse_limit_validator("10..."), (10, -1, -1));
-        assert_eq!(parse_limit_validator("1..10..5"), (1, 5, 10));
-        assert_eq!(parse_limit_validator("1.."), (1, -1, 1));
-        assert_eq!(parse_limit_validator("..10"), (-1, 10, 10));
+        assert_eq!(parse_limit_validator("1...10"), Some((1, 10, -1)));
+        assert_eq!(parse_limit_validator("...10"), Some((-1, 10, -1)));
+        assert_eq!(parse_limit_validator("10..."), Some((10, -1, -1)));
+        assert_eq!(parse_limit_validator("1..10..5"), Some((1, 5, 10)));
+        assert_eq!(parse_limit_validator("1.."), Some((1, -1, 1)));
+        assert_eq!(parse_limit_validator("..10"), Some((-1, 10, 10)));
+        assert_eq!(parse_limit_validator("1..x..5"), None);
+        assert_eq!(parse_limit_validator("abc...10"), None);
+        assert_eq!(parse_limit_validator("10"), None);
     }
 }
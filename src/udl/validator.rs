@@ -0,0 +1,720 @@
+use std::collections::HashSet;
+
+use convert_case::ccase;
+use regex::Regex;
+
+use crate::udl::{
+    UDL, UdlError,
+    class::{Class, PropertyKey},
+    constraints::Format,
+    enums::{Enum, EnumKind, EnumVariant},
+    interface::{Interface, MethodParams, ReturnType},
+    types::{TypeExpr, TypeKind},
+    utils::{PRIMITIVES, parse_limit_validator, parse_type},
+};
+
+/// Runs every semantic check over a parsed manifest and returns all problems found.
+pub fn validate(udl: &UDL) -> Vec<UdlError> {
+    let mut errors = Vec::new();
+    check_duplicates(udl, &mut errors);
+    check_defaults(udl, &mut errors);
+    for class in &udl.models {
+        check_class(udl, class, &mut errors);
+    }
+    for enumm in &udl.enums {
+        check_tag_fields(enumm, &mut errors);
+        check_error_targets(udl, enumm, &mut errors);
+    }
+    for interface in &udl.interfaces {
+        check_interface(udl, interface, &mut errors);
+    }
+    errors
+}
+
+fn check_duplicates(udl: &UDL, errors: &mut Vec<UdlError>) {
+    let mut seen = HashSet::new();
+    for (index, enumm) in udl.enums.iter().enumerate() {
+        if !seen.insert(enumm.id.as_str()) {
+            errors.push(UdlError::DuplicateId {
+                kind: "enum",
+                id: enumm.id.clone(),
+                index,
+            });
+        }
+        let mut variants = HashSet::new();
+        for variant in &enumm.variants {
+            if !variants.insert(variant.id()) {
+                errors.push(UdlError::DuplicateVariant {
+                    enum_id: enumm.id.clone(),
+                    variant: variant.id().to_string(),
+                });
+            }
+        }
+    }
+    for (index, class) in udl.models.iter().enumerate() {
+        if !seen.insert(class.id.as_str()) {
+            errors.push(UdlError::DuplicateId {
+                kind: "model",
+                id: class.id.clone(),
+                index,
+            });
+        }
+    }
+    for (index, interface) in udl.interfaces.iter().enumerate() {
+        if !seen.insert(interface.id.as_str()) {
+            errors.push(UdlError::DuplicateId {
+                kind: "interface",
+                id: interface.id.clone(),
+                index,
+            });
+        }
+    }
+}
+
+/// Checks the `defaults:` section and every enum's `default:`.
+fn check_defaults(udl: &UDL, errors: &mut Vec<UdlError>) {
+    for (type_name, value) in &udl.defaults {
+        let valid = match udl.enums.iter().find(|e| &e.id == type_name) {
+            Some(enumm) => is_unit_variant(enumm, value),
+            None if PRIMITIVES.contains(&type_name.as_str()) => {
+                parse_type(type_name).is_some_and(|ty| is_valid_default(udl, &ty, value))
+            }
+            None => {
+                errors.push(UdlError::UnknownDefaultType {
+                    type_name: type_name.clone(),
+                });
+                continue;
+            }
+        };
+        if !valid {
+            errors.push(UdlError::InvalidTypeDefault {
+                type_name: type_name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+    for enumm in &udl.enums {
+        let Some(value) = &enumm.default else {
+            continue;
+        };
+        match enumm.find_variant(value) {
+            None => errors.push(UdlError::InvalidEnumDefault {
+                enum_id: enumm.id.clone(),
+                value: value.clone(),
+            }),
+            Some(variant) if variant.fields().is_some() => {
+                errors.push(UdlError::EnumDefaultWithFields {
+                    enum_id: enumm.id.clone(),
+                    value: value.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// Whether `value` names a variant of `enumm` without fields. Variants with fields
+/// have no value to fill them with, so they can't be defaults.
+fn is_unit_variant(enumm: &Enum, value: &str) -> bool {
+    enumm
+        .find_variant(value)
+        .is_some_and(|variant| variant.fields().is_none())
+}
+
+/// Types nested in `ty` that resolve to nothing: unknown models and enums, `void`,
+/// and types with arguments other than collections.
+fn unresolved_types<'a>(udl: &UDL, ty: &'a TypeExpr) -> Vec<&'a TypeExpr> {
+    let mut unresolved = Vec::new();
+    ty.visit(&mut |ty| {
+        let resolved = match &ty.kind {
+            TypeKind::Model(id) => udl.models.iter().any(|m| &m.id == id),
+            TypeKind::Enum(id) => udl.enums.iter().any(|e| &e.id == id),
+            TypeKind::Generic { .. } | TypeKind::Void => false,
+            _ => true,
+        };
+        if !resolved {
+            unresolved.push(ty);
+        }
+    });
+    unresolved
+}
+
+fn check_class(udl: &UDL, class: &Class, errors: &mut Vec<UdlError>) {
+    if let Some(error) = &class.error
+        && !udl.enums.iter().any(|e| &e.id == error)
+    {
+        errors.push(UdlError::MissingErrorEnum {
+            model: class.id.clone(),
+            error: error.clone(),
+        });
+    }
+
+    for (name, prop) in &class.properties {
+        let Some(type_name) = prop.type_name() else {
+            errors.push(UdlError::MissingType {
+                model: class.id.clone(),
+                property: name.clone(),
+            });
+            continue;
+        };
+
+        let ty = parse_type(type_name);
+        match &ty {
+            Some(ty) => {
+                for referenced in unresolved_types(udl, ty) {
+                    errors.push(match &referenced.kind {
+                        TypeKind::Enum(enum_id) => UdlError::UnknownEnum {
+                            model: class.id.clone(),
+                            property: name.clone(),
+                            enum_id: enum_id.clone(),
+                        },
+                        _ => UdlError::UnknownType {
+                            model: class.id.clone(),
+                            property: name.clone(),
+                            type_name: referenced.name().to_string(),
+                        },
+                    });
+                }
+                // Set elements and map keys are hashed in every target language
+                let mut keys = Vec::new();
+                ty.visit(&mut |ty| match &ty.kind {
+                    TypeKind::Set(item) => keys.push(item),
+                    TypeKind::Map(key, _) => keys.push(key),
+                    _ => {}
+                });
+                for key in keys {
+                    if let Some(type_name) = udl.unhashable_type(key) {
+                        errors.push(UdlError::UnhashableType {
+                            model: class.id.clone(),
+                            property: name.clone(),
+                            type_name,
+                        });
+                    }
+                }
+            }
+            None => errors.push(UdlError::InvalidType {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: type_name.to_string(),
+            }),
+        }
+
+        // The middle value of a numeric limit is the property default, so it has to
+        // lie within the bounds and fit the type
+        let invalid_default = |(min, max, default): (isize, isize, isize)| {
+            ty.as_ref().is_some_and(|ty| {
+                ty.is_number()
+                    && default != -1
+                    && (default < min
+                        || (max != -1 && default > max)
+                        || !is_valid_default(udl, ty, &default.to_string()))
+            })
+        };
+        if let Some(value) = prop.get(&PropertyKey::Limit)
+            && parse_limit_validator(value).is_none_or(invalid_default)
+        {
+            errors.push(UdlError::InvalidLimit {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: value.to_string(),
+            });
+        }
+        for key in [PropertyKey::Min, PropertyKey::Max] {
+            if let Some(value) = prop.get(&key)
+                && value.parse::<isize>().is_err()
+            {
+                errors.push(UdlError::InvalidNumber {
+                    model: class.id.clone(),
+                    property: name.clone(),
+                    key: format!("{:?}", key).to_lowercase(),
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        if let Some(value) = prop.get(&PropertyKey::Format)
+            && Format::parse(value).is_none()
+        {
+            errors.push(UdlError::UnknownFormat {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: value.to_string(),
+            });
+        }
+
+        // Patterns are limited to the syntax of the `regex` crate, which the other
+        // targets' engines also understand: no look-around or backreferences
+        if let Some(value) = prop.get(&PropertyKey::Pattern)
+            && let Err(error) = Regex::new(value)
+        {
+            let reason = error.to_string();
+            errors.push(UdlError::InvalidPattern {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: value.to_string(),
+                reason: reason
+                    .lines()
+                    .last()
+                    .map(|line| line.trim_start_matches("error: "))
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+
+        if let Some(value) = prop.get(&PropertyKey::Default)
+            && let Some(ty) = &ty
+            && !is_valid_default(udl, ty, value)
+        {
+            errors.push(UdlError::InvalidDefault {
+                model: class.id.clone(),
+                property: name.clone(),
+                value: value.to_string(),
+                type_name: type_name.to_string(),
+            });
+        }
+    }
+}
+
+/// Whether `value` is a legal literal for `ty`. Only strings, bools, numbers and
+/// unit variants have a literal in every target, so types such as `decimal` or
+/// `uuid` take no default. Unknown enums are reported separately and accepted here.
+fn is_valid_default(udl: &UDL, ty: &TypeExpr, value: &str) -> bool {
+    if value == "null" && ty.nullable {
+        return true;
+    }
+    match &ty.kind {
+        TypeKind::Enum(enum_id) => match udl.enums.iter().find(|e| &e.id == enum_id) {
+            Some(enumm) => is_unit_variant(enumm, value),
+            None => true,
+        },
+        TypeKind::Primitive(name) => match name.as_str() {
+            "bool" => matches!(value, "true" | "false"),
+            "string" | "email" => true,
+            "float" | "float32" | "float64" => value.parse::<f64>().is_ok(),
+            "int" | "int32" => value.parse::<i32>().is_ok(),
+            "int8" => value.parse::<i8>().is_ok(),
+            "int16" => value.parse::<i16>().is_ok(),
+            "int64" | "isize" => value.parse::<i64>().is_ok(),
+            "uint8" => value.parse::<u8>().is_ok(),
+            "uint16" => value.parse::<u16>().is_ok(),
+            "uint32" => value.parse::<u32>().is_ok(),
+            "uint64" | "usize" => value.parse::<u64>().is_ok(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Checks that no variant field of a tagged enum is written as `type`, the key
+/// carrying the variant's wire value.
+fn check_tag_fields(enumm: &Enum, errors: &mut Vec<UdlError>) {
+    for variant in &enumm.variants {
+        for key in variant
+            .fields()
+            .into_iter()
+            .flat_map(|fields| fields.keys())
+        {
+            if ccase!(snake, key) == "type" {
+                errors.push(UdlError::TagField {
+                    enum_id: enumm.id.clone(),
+                    variant: variant.id().to_string(),
+                    field: key.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Checks that `target` and `target_field` of error variants refer to validators
+/// and properties of the models using the enum as their `error:`.
+fn check_error_targets(udl: &UDL, enumm: &Enum, errors: &mut Vec<UdlError>) {
+    let classes = udl
+        .models
+        .iter()
+        .filter(|class| class.error.as_ref() == Some(&enumm.id))
+        .collect::<Vec<_>>();
+
+    for variant in &enumm.variants {
+        let EnumKind::Complex(EnumVariant {
+            id,
+            target,
+            target_field,
+            ..
+        }) = variant
+        else {
+            continue;
+        };
+
+        let properties = classes
+            .iter()
+            .flat_map(|class| &class.properties)
+            .filter(|(name, _)| target_field.as_ref().is_none_or(|field| field == *name))
+            .map(|(_, prop)| prop)
+            .collect::<Vec<_>>();
+
+        if let Some(field) = target_field
+            && !classes.is_empty()
+            && properties.is_empty()
+        {
+            errors.push(UdlError::UnknownTargetField {
+                enum_id: enumm.id.clone(),
+                variant: id.clone(),
+                field: field.clone(),
+            });
+            continue;
+        }
+
+        let Some(target) = target else {
+            continue;
+        };
+        let known = match target.split_once(':') {
+            Some(("limit", bound @ ("min" | "max"))) => {
+                classes.is_empty()
+                    || properties.iter().any(|prop| {
+                        let key = if bound == "min" {
+                            PropertyKey::Min
+                        } else {
+                            PropertyKey::Max
+                        };
+                        let limit = prop
+                            .get(&PropertyKey::Limit)
+                            .and_then(parse_limit_validator)
+                            .map(|(min, max, _)| if bound == "min" { min } else { max });
+                        prop.get(&key).is_some() || limit.is_some_and(|limit| limit != -1)
+                    })
+            }
+            Some(("format", format)) => {
+                classes.is_empty()
+                    || properties
+                        .iter()
+                        .any(|prop| prop.get(&PropertyKey::Format) == Some(format))
+            }
+            None if target == "pattern" => {
+                classes.is_empty()
+                    || properties
+                        .iter()
+                        .any(|prop| prop.get(&PropertyKey::Pattern).is_some())
+            }
+            _ => false,
+        };
+        if !known {
+            errors.push(UdlError::UnknownTarget {
+                enum_id: enumm.id.clone(),
+                variant: id.clone(),
+                target: target.clone(),
+            });
+        }
+    }
+}
+
+fn check_interface(udl: &UDL, interface: &Interface, errors: &mut Vec<UdlError>) {
+    let mut methods = HashSet::new();
+    for method in &interface.methods {
+        if !methods.insert(method.id.as_str()) {
+            errors.push(UdlError::DuplicateMethod {
+                interface: interface.id.clone(),
+                method: method.id.clone(),
+            });
+        }
+
+        let unresolved = |errors: &mut Vec<UdlError>, field: &'static str, ty: &TypeExpr| {
+            for referenced in unresolved_types(udl, ty) {
+                errors.push(UdlError::UnknownMethodType {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    field,
+                    type_name: match &referenced.kind {
+                        TypeKind::Enum(enum_id) => format!("$enum::{}", enum_id),
+                        _ => referenced.name().to_string(),
+                    },
+                });
+            }
+        };
+
+        let params = match &method.params {
+            None => vec![],
+            Some(MethodParams::Type(ty)) => vec![ty],
+            Some(MethodParams::Inline(params)) => params.values().collect(),
+        };
+        for param in params {
+            match parse_type(param) {
+                Some(ty) => unresolved(errors, "params", &ty),
+                None => errors.push(UdlError::InvalidParams {
+                    interface: interface.id.clone(),
+                    method: method.id.clone(),
+                    value: param.clone(),
+                }),
+            }
+        }
+
+        let returns = match method.return_type() {
+            Some(ReturnType::Void) => Some(vec![]),
+            Some(ReturnType::Type(ty)) => Some(vec![ty]),
+            Some(ReturnType::Result { ok, err }) => Some(ok.into_iter().chain([err]).collect()),
+            None => None,
+        };
+        match returns {
+            Some(returns) => {
+                for ty in &returns {
+                    unresolved(errors, "returns", ty);
+                }
+            }
+            None => errors.push(UdlError::InvalidReturnType {
+                interface: interface.id.clone(),
+                method: method.id.clone(),
+                value: method.returns.clone().unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> UDL {
+        serde_yaml::from_str(source).unwrap()
+    }
+
+    const HEADER: &str = r#"
+udl_version: 0.0.1
+project:
+  name: Test
+  version: 0.0.1
+  description: Test
+  namespace: com.example.test
+  models_only: true
+  target_platforms: []
+  authors: []
+  license: MIT
+"#;
+
+    #[test]
+    fn test_examples_are_valid() {
+        for path in ["examples/billing_app.yaml", "examples/udl-example.udl.yaml"] {
+            let udl = parse(&std::fs::read_to_string(path).unwrap());
+            let errors = validate(&udl);
+            assert!(errors.is_empty(), "{}: {:?}", path, errors);
+        }
+    }
+
+    #[test]
+    fn test_reports_all_problems() {
+        let udl = parse(&format!(
+            "{HEADER}{}",
+            r#"
+defaults:
+  bool: "no"
+  SortOrder: K_ASC
+  Money: "0"
+enums:
+  - id: SortOrder
+    default: K_NONE
+    variants:
+      - K_ASC
+      - K_DESC
+  - id: LoginError
+    variants:
+      - id: K_INVALID_EMAIL
+        value: "Invalid email"
+        target: "format:email"
+      - id: K_SHORT
+        value: "Too short"
+        target: "limit:min"
+        target_field: "passwd"
+      - id: K_UNKNOWN
+        value: "Unknown"
+        target: "regex:foo"
+      - id: K_PATTERN
+        value: "No match"
+        target: "pattern"
+  - id: Event
+    variants:
+      - id: K_MOVED
+        value:
+          type: string
+          to: string
+models:
+  - id: LoginRequest
+    error: LoginError
+    properties:
+      email: string
+      password:
+        type: string
+        limit: 8...32
+      status: $enum::LoginStatus
+      items: List<InvoiceItem>
+      page:
+        type: uint8
+        limit: 1..300..500
+      offset:
+        type: int
+        limit: 1..0..10
+      order:
+        type: $enum::SortOrder
+        default: descending
+      retries:
+        type: uint8
+        default: "-1"
+      price:
+        type: decimal
+        default: "1.50"
+      tags: List<string, int>
+      phone:
+        type: string
+        format: telephone
+      lookup: Map<string, List<$enum::Tag?>>
+  - id: Profile
+    error: ProfileError
+    properties:
+      name: string
+      code:
+        type: string
+        pattern: "^(?!0)[0-9]+$"
+      zip:
+        type: string
+        pattern: "[0-9"
+  - id: SortOrder
+    properties:
+      id: string
+interface:
+  - id: AuthService
+    methods:
+      - id: login
+        params: LoginRequest
+        returns: Result<User, $enum::ApiError>
+      - id: login
+        params:
+          token: Token
+        returns: Result<LoginRequest>
+"#
+        ));
+        let mut errors = validate(&udl)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "defaults: `no` is not a valid `bool`",
+                "defaults: unknown type `Money`",
+                "duplicate model id `SortOrder`",
+                "enum `Event`, variant `K_MOVED`: field `type` collides with the `type` tag",
+                "enum `LoginError`, variant `K_INVALID_EMAIL`: target `format:email` does not match any validator",
+                "enum `LoginError`, variant `K_PATTERN`: target `pattern` does not match any validator",
+                "enum `LoginError`, variant `K_SHORT`: `target_field` `passwd` is not a property of a model using it",
+                "enum `LoginError`, variant `K_UNKNOWN`: target `regex:foo` does not match any validator",
+                "enum `SortOrder`: default `K_NONE` is not a variant",
+                "interface `AuthService`, method `login`: `params` references unknown type `Token`",
+                "interface `AuthService`, method `login`: `returns` references unknown type `$enum::ApiError`",
+                "interface `AuthService`, method `login`: `returns` references unknown type `User`",
+                "interface `AuthService`, method `login`: duplicate method",
+                "interface `AuthService`, method `login`: invalid return type `Result<LoginRequest>`",
+                "model `LoginRequest`, property `items`: unknown type `InvoiceItem`",
+                "model `LoginRequest`, property `lookup`: unknown enum `Tag`",
+                "model `LoginRequest`, property `offset`: invalid limit `1..0..10`",
+                "model `LoginRequest`, property `order`: default `descending` is not a valid `$enum::SortOrder`",
+                "model `LoginRequest`, property `page`: invalid limit `1..300..500`",
+                "model `LoginRequest`, property `phone`: unknown format `telephone`",
+                "model `LoginRequest`, property `price`: default `1.50` is not a valid `decimal`",
+                "model `LoginRequest`, property `retries`: default `-1` is not a valid `uint8`",
+                "model `LoginRequest`, property `status`: unknown enum `LoginStatus`",
+                "model `LoginRequest`, property `tags`: invalid type `List<string, int>`",
+                "model `Profile`, property `code`: invalid pattern `^(?!0)[0-9]+$`: look-around, including look-ahead and look-behind, is not supported",
+                "model `Profile`, property `zip`: invalid pattern `[0-9`: unclosed character class",
+                "model `Profile`: error enum `ProfileError` is not defined",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unhashable_keys() {
+        let udl = parse(&format!(
+            "{HEADER}{}",
+            r#"
+enums:
+  - id: Shape
+    variants:
+      - K_NONE
+      - id: K_CIRCLE
+        value:
+          radius: float
+models:
+  - id: Tag
+    properties:
+      name: string
+      color: List<uint8>?
+  - id: Point
+    properties:
+      x: float64
+      tags: Set<Tag>
+  - id: Canvas
+    properties:
+      prices: Map<float, string>
+      points: Set<Point>
+      shapes: List<Set<$enum::Shape>>
+      nested: Map<Set<int>, int>
+      lookup: Map<string, Set<Tag>>
+"#
+        ));
+        let mut errors = validate(&udl)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "model `Canvas`, property `nested`: `Set<int>` cannot be part of a set element or map key",
+                "model `Canvas`, property `points`: `float64` cannot be part of a set element or map key",
+                "model `Canvas`, property `prices`: `float` cannot be part of a set element or map key",
+                "model `Canvas`, property `shapes`: `float` cannot be part of a set element or map key",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_defaults_with_fields() {
+        let udl = parse(&format!(
+            "{HEADER}{}",
+            r#"
+defaults:
+  Shape: K_SQUARE
+enums:
+  - id: Shape
+    default: K_CIRCLE
+    variants:
+      - K_NONE
+      - id: K_CIRCLE
+        value:
+          radius: float
+      - id: K_SQUARE
+        value:
+          side: float
+models:
+  - id: Canvas
+    properties:
+      shape:
+        type: $enum::Shape
+        default: K_CIRCLE
+      outline:
+        type: $enum::Shape
+        default: K_NONE
+"#
+        ));
+        let mut errors = validate(&udl)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "defaults: `K_SQUARE` is not a valid `Shape`",
+                "enum `Shape`: default `K_CIRCLE` is a variant with fields",
+                "model `Canvas`, property `shape`: default `K_CIRCLE` is not a valid `$enum::Shape`",
+            ]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Crate the Rust generated for the test manifests is compiled in, see
+//! `test_generated_code_round_trips` in `src/rust/mod.rs`. Running it checks that JSON
+//! fixtures survive a round trip through the generated types.
+#![deny(warnings)]
+#![allow(dead_code, unused_imports)]
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+mod billing_app {
+    include!(env!("UDL_BILLING_APP"));
+}
+
+mod collections {
+    include!(env!("UDL_COLLECTIONS"));
+}
+
+mod ordered_collections {
+    include!(env!("UDL_ORDERED_COLLECTIONS"));
+}
+
+/// Generated with `--rust-datetime time`
+mod time_collections {
+    include!(env!("UDL_TIME_COLLECTIONS"));
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(json: &str) {
+    let value = serde_json::from_str::<T>(json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        serde_json::from_str::<Value>(json).unwrap(),
+        "{}",
+        std::any::type_name::<T>()
+    );
+}
+
+fn main() {
+    for label in [
+        r#"{"name": "a", "color": null, "created_at": "2024-01-15T10:30:00Z",
+            "archived_at": null}"#,
+        r#"{"name": "a", "color": [255, 0, 0], "created_at": "2024-01-15T10:30:00Z",
+            "archived_at": "2024-03-01T00:00:00Z", "due_on": "2024-02-01"}"#,
+    ] {
+        round_trip::<collections::Label>(label);
+        round_trip::<time_collections::Label>(label);
+    }
+    round_trip::<collections::Token>(r#"{"type": "bearer", "self": true, "match": null}"#);
+    // Validated models are decoded through `build()`
+    round_trip::<billing_app::LoginRequest>(
+        r#"{"email": "a@b.co", "password": "12345678", "remember_me": false}"#,
+    );
+    let error = serde_json::from_str::<billing_app::LoginRequest>(
+        r#"{"email": "a@b.co", "password": "1234"}"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Invalid password minimum length"));
+    // Tagged enums, in the JSON the Dart generator is checked against too
+    let receipts = serde_json::from_str::<Vec<Value>>(include_str!("../receipts.json")).unwrap();
+    for receipt in receipts {
+        round_trip::<collections::Receipt>(&receipt.to_string());
+    }
+    // Dart writes missing optional bytes as null
+    serde_json::from_str::<collections::Receipt>(
+        r#"{"payment": {"type": "K_NONE"}, "refunds": [], "scan": null}"#,
+    )
+    .unwrap();
+}